@@ -1,17 +1,31 @@
 use crate::{
     component::{
+        aggregation_viewer, cache_admin_viewer, compare_viewer, containment_viewer,
+        element_detail_viewer, impact_viewer,
         element_viewer::ElementViewerInput,
+        facet_schema_viewer, graph_viewer,
         json_viewer::{self},
-        model_stats_viewer, relationship_viewer, status_toast_viewer,
+        meta_graph_viewer, model_stats_viewer, relationship_viewer, stats_viewer, status_toast_viewer,
+        search_viewer, table_viewer, value_breakdown_viewer,
+    },
+    model::{
+        cubs_model::{self, FacetType, ModelData, ModelVersionNumber},
+        element_diff::PinnedElement,
+        element_table::ElementTable,
+        model_dict::ValueBreakdown,
+        saved_query::SavedQuery,
+        value_stats::NumericStats,
+        view_link::{self, ViewLink},
     },
-    model::cubs_model::{self, FacetType, ModelData, ModelVersionNumber},
 };
 use leptos::logging::log;
 use leptos::prelude::*;
 use leptos_meta::{provide_meta_context, Stylesheet, Title};
 use leptos_router::{
-    components::{Route, Router, Routes},
-    StaticSegment, WildcardSegment,
+    components::{ParentRoute, Route, Router, Routes},
+    hooks::{use_location, use_navigate},
+    location::Url,
+    NavigateOptions, ParamSegment, StaticSegment, WildcardSegment,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -28,12 +42,25 @@ pub struct ServerResult {
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct QueryResult {
-    pub data: String,
+    pub data: Value,
     pub duration: String,
     pub result_count: usize,
     pub total_result_count: usize,
+    pub stats: Option<NumericStats>,
+    pub breakdown: Option<ValueBreakdown>,
+    pub table: Option<ElementTable>,
 }
 
+// Value of the query mode selector returning distinct values instead of elements
+pub const VALUE_BREAKDOWN_MODE: &str = "breakdown";
+// Value of the query mode selector returning one row per element
+pub const TABLE_MODE: &str = "table";
+
+// Defaults of the query form
+const DEFAULT_MODEL_ID: &str = "4fd3dccd-9b87-4fde-9b50-db4f57ab10e6";
+pub const DEFAULT_QUERY_DEPTH: usize = 3;
+pub const DEFAULT_QUERY_LIMIT: usize = 30;
+
 #[derive(Debug, Clone)]
 pub enum StatusMsg {
     OK(String),
@@ -56,6 +83,17 @@ impl StatusMsg {
 pub enum RHSMode {
     ModelStats,
     Rel(String),
+    CacheAdmin,
+    ValueBreakdown,
+    FacetSchema,
+    Aggregation,
+    Search,
+    ElementDetail,
+    Compare,
+    Graph,
+    Containment,
+    MetaGraph,
+    Impact,
     Default,
 }
 
@@ -76,7 +114,28 @@ pub fn App() -> impl IntoView {
         <Router>
             <main>
                 <Routes fallback=move || "Not found.">
-                    <Route path=StaticSegment("") view=HomePage />
+                    // Deep links are child routes to keep the home page mounted while navigating
+                    <ParentRoute path=StaticSegment("") view=HomePage>
+                        <Route path=StaticSegment("") view=|| () />
+                        <Route
+                            path=(
+                                StaticSegment(view_link::MODEL_SEGMENT),
+                                ParamSegment("id"),
+                                ParamSegment("version"),
+                            )
+                            view=|| ()
+                        />
+                        <Route
+                            path=(
+                                StaticSegment(view_link::MODEL_SEGMENT),
+                                ParamSegment("id"),
+                                ParamSegment("version"),
+                                StaticSegment(view_link::ELEMENT_SEGMENT),
+                                ParamSegment("eid"),
+                            )
+                            view=|| ()
+                        />
+                    </ParentRoute>
                     <Route path=WildcardSegment("any") view=NotFound />
                 </Routes>
             </main>
@@ -102,7 +161,7 @@ fn HomePage() -> impl IntoView {
 
     let (stats, set_stats) = signal("".to_string());
     let (duration, set_duration) = signal("".to_string());
-    let (query, set_query) = signal("".to_string());
+    let (query, set_query) = signal(None::<Value>);
     let (element_type, set_element_type): (ReadSignal<Vec<String>>, WriteSignal<Vec<String>>) =
         signal(vec!["".to_string()]);
     let (element_nature, set_element_nature): (ReadSignal<Vec<String>>, WriteSignal<Vec<String>>) =
@@ -111,11 +170,118 @@ fn HomePage() -> impl IntoView {
     let (status_bar, set_status_bar) = signal(StatusMsg::Empty);
     let (result_count, set_result_count) = signal(0 as usize);
     let (total_result_count, set_total_result_count) = signal(0 as usize);
+    let (query_stats, set_query_stats) = signal(None::<NumericStats>);
+    let (value_breakdown, set_value_breakdown) = signal(None::<ValueBreakdown>);
+    let (last_query, set_last_query) = signal(None::<QueryModel>);
+    // Query of the results shown, pointers in the results are relative to it
+    let (result_query, set_result_query) = signal(None::<QueryModel>);
+    let (element_table, set_element_table) = signal(None::<ElementTable>);
+    let is_table_result = Memo::new(move |_| element_table.with(|t| t.is_some()));
+    let (query_pointer, set_query_pointer) = signal("".to_string());
+    let (query_facet_type, set_query_facet_type) = signal("".to_string());
 
     let (selected_object_id, set_selected_object_id): (ReadSignal<String>, WriteSignal<String>) =
         signal("".to_string());
 
     let (rhs_mode, set_rhs_mode) = signal(RHSMode::Default);
+    let (pinned, set_pinned) = signal(Vec::<PinnedElement>::new());
+
+    // Deep link of the current URL, restored once its model version is read
+    let location = use_location();
+    let navigate = use_navigate();
+    let current_link = Memo::new(move |_| {
+        let segments = view_link::get_path_segments(&location.pathname.get(), Url::unescape);
+        let filters = location.query.with(|query| {
+            query
+                .clone()
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect()
+        });
+        ViewLink::from_segments(&segments, filters)
+    });
+    let pending_link = StoredValue::new(None::<ViewLink>);
+
+    // Restore the selected element and the query of a deep link. The view only changes
+    // for a newly read model or links selecting another element, i.e. on load or when
+    // navigating the history, not for the links pushed by an in-app selection
+    let apply_link = move |link: ViewLink, is_new_model: bool| {
+        if is_new_model || link.is_selection_change(&selected_object_id.get_untracked()) {
+            match &link.element_id {
+                Some(element_id) => {
+                    set_selected_object_id.set(element_id.clone());
+                    set_rhs_mode.set(RHSMode::Rel(element_id.clone()));
+                }
+                None => {
+                    set_selected_object_id.set(String::new());
+                    if matches!(rhs_mode.get_untracked(), RHSMode::Rel(_)) {
+                        set_rhs_mode.set(RHSMode::ModelStats);
+                    }
+                }
+            }
+        }
+
+        let link_query = get_link_query(&link);
+        let link_filters = link_query.as_ref().map(get_query_filters);
+        if link_filters != last_query.with_untracked(|q| q.as_ref().map(get_query_filters)) {
+            set_last_query.set(link_query.clone());
+            set_result_query.set(link_query.clone());
+            set_query.set(None);
+            set_element_table.set(None);
+            if let Some(input) = link_query {
+                set_query_facet_type.set(input.facet_type.clone());
+                set_query_pointer.set(input.query.clone());
+                query_model_action.dispatch(input);
+            }
+        }
+    };
+
+    // Read the model of a new deep link, e.g. on load or when navigating the history
+    Effect::new(move |_| {
+        let Some(link) = current_link.get() else {
+            return;
+        };
+        if link.model_id != model_id.get_untracked()
+            || link.version != selected_version.get_untracked()
+        {
+            pending_link.set_value(Some(link.clone()));
+            parse_model_action.dispatch(ParseModel {
+                model_id: link.model_id,
+                vers_no: link.version,
+            });
+        } else {
+            apply_link(link, false);
+        }
+    });
+
+    // Push the link of the current view to the history
+    Effect::new(move |_| {
+        let model_id = model_id.get();
+        if model_id.is_empty() || pending_link.with_value(|l| l.is_some()) {
+            return;
+        }
+        let filters = last_query.with(|q| {
+            q.as_ref()
+                .filter(|q| q.model_id == model_id)
+                .map(get_query_filters)
+                .unwrap_or_default()
+        });
+        let link = ViewLink {
+            model_id,
+            version: selected_version.get(),
+            element_id: Some(selected_object_id.get()).filter(|id| !id.is_empty()),
+            filters,
+        };
+        if current_link.get_untracked().as_ref() != Some(&link) {
+            navigate(
+                &link.to_path(Url::escape),
+                NavigateOptions {
+                    scroll: false,
+                    ..Default::default()
+                },
+            );
+        }
+    });
 
     let parsed_json_stats = Memo::new(move |_| {
         let stats_str = stats.get();
@@ -139,10 +305,17 @@ fn HomePage() -> impl IntoView {
                         Some(v) => v.clone(),
                         None => 0.to_string(),
                     };
+                    // Keep the version of a deep link being restored
+                    let link = pending_link.try_update_value(|l| l.take()).flatten();
+                    let version = link
+                        .as_ref()
+                        .map(|l| l.version.clone())
+                        .filter(|v| versions.contains(v))
+                        .unwrap_or(latest_version);
                     set_model_versions.set(versions);
-                    set_selected_version.set(latest_version);
+                    set_selected_version.set(version);
                     set_duration.set(result.duration.clone());
-                    set_query.set(String::new());
+                    set_query.set(None);
 
                     // Type and natures
                     let mut types = result.types;
@@ -156,8 +329,13 @@ fn HomePage() -> impl IntoView {
 
                     //Set status bar
                     set_status_bar.set(StatusMsg::Empty);
+
+                    if let Some(link) = link {
+                        apply_link(link, true);
+                    }
                 }
                 Err(e) => {
+                    pending_link.set_value(None);
                     let err: ServerFnError = e;
                     log!("[Error] {}", err.to_string());
                     set_status_bar.set(StatusMsg::Error(err.to_string()));
@@ -166,15 +344,143 @@ fn HomePage() -> impl IntoView {
         }
     });
 
+    // Keep the last submitted query to rerun it with another filter
+    let query_input = query_model_action.input();
     Effect::new(move |_| {
-        if let Some(Ok(result)) = query_value.get() {
-            set_query.set(result.data);
+        if let Some(input) = query_input.get() {
+            set_result_query.set(Some(input.clone()));
+            set_last_query.set(Some(input));
+        }
+    });
+
+    Effect::new(move |_| match query_value.get() {
+        Some(Ok(result)) => {
+            set_query.set(Some(result.data));
             set_duration.set(result.duration);
             set_result_count.set(result.result_count);
             set_total_result_count.set(result.total_result_count);
+            set_query_stats.set(result.stats);
+            set_element_table.set(result.table);
+            if result.breakdown.is_some() {
+                set_value_breakdown.set(result.breakdown);
+                set_rhs_mode.set(RHSMode::ValueBreakdown);
+            }
+        }
+        Some(Err(e)) => {
+            log!("[Error] {}", e.to_string());
+            set_query.set(None);
+            set_status_bar.set(StatusMsg::Error(e.to_string()));
+        }
+        None => {}
+    });
+
+    // Rerun the last query with elements having the value
+    let filter_by_value = Callback::new(move |value_key: String| {
+        if let Some(mut input) = last_query.get_untracked() {
+            set_status_bar.set(StatusMsg::Info(format!("Filtering by value {}", value_key)));
+            input.mode = None;
+            input.value_filter = Some(value_key);
+            set_query.set(None);
+            query_model_action.dispatch(input);
+        }
+    });
+
+    // Query a single element with the default output filtering
+    let show_element = Callback::new(move |id: String| {
+        set_selected_object_id.set(id.clone());
+        set_query.set(None);
+        query_model_action.dispatch(QueryModel {
+            model_id: model_id.get_untracked(),
+            vers_no: selected_version.get_untracked(),
+            id,
+            types: "All".to_string(),
+            natures: "All".to_string(),
+            query: String::new(),
+            depth: DEFAULT_QUERY_DEPTH,
+            limit: DEFAULT_QUERY_LIMIT,
+            facet_type: String::new(),
+            is_detail: None,
+            mode: None,
+            value_filter: None,
+            columns: None,
+        });
+    });
+
+    // Run a saved query against the selected model version
+    let run_saved_query = Callback::new(move |saved: SavedQuery| {
+        set_status_bar.set(StatusMsg::Info(format!("Running saved query {}", saved.name)));
+        set_query.set(None);
+        query_model_action.dispatch(QueryModel {
+            model_id: model_id.get_untracked(),
+            vers_no: selected_version.get_untracked(),
+            id: String::new(),
+            types: saved.types,
+            natures: saved.natures,
+            query: saved.query,
+            depth: saved.depth.max(0) as usize,
+            limit: saved.limit.max(0) as usize,
+            facet_type: saved.facet_type,
+            is_detail: None,
+            mode: None,
+            value_filter: None,
+            columns: None,
+        });
+    });
+
+    // Pin an element of the selected model version for comparison
+    let pin_element = Callback::new(move |id: String| {
+        let element = PinnedElement {
+            model_id: model_id.get_untracked(),
+            version: selected_version.get_untracked(),
+            id,
+        };
+        let count = pinned.with_untracked(|p| p.len());
+        if pinned.with_untracked(|p| p.contains(&element)) {
+            set_status_bar.set(StatusMsg::Info(format!("{} is already pinned", element.id)));
+        } else if count >= compare_viewer::MAX_PINNED_ELEMENTS {
+            set_status_bar.set(StatusMsg::Error(format!(
+                "Unpin an element to compare more than {} elements",
+                compare_viewer::MAX_PINNED_ELEMENTS
+            )));
+        } else {
+            set_status_bar.set(StatusMsg::Info(format!(
+                "Pinned {} ({} pinned)",
+                element.id,
+                count + 1
+            )));
+            set_pinned.update(|p| p.push(element));
+        }
+    });
+
+    // Show the elements of an aggregation group
+    let show_group_elements = Callback::new(move |(elements, total): (Vec<Value>, usize)| {
+        set_result_count.set(elements.len());
+        set_total_result_count.set(total);
+        set_query_stats.set(None);
+        set_element_table.set(None);
+        // Results are elements, keep key pointers relative to them. The last query is
+        // kept as is, its filter is still the one of the view
+        set_result_query.set(last_query.get_untracked().map(|q| QueryModel {
+            facet_type: String::new(),
+            query: String::new(),
+            ..q
+        }));
+        set_query.set(Some(Value::Array(elements)));
+    });
+
+    // Rerun the last query on a path picked from the results
+    let query_path = Callback::new(move |(facet_type, pointer): (String, String)| {
+        set_query_facet_type.set(facet_type.clone());
+        set_query_pointer.set(pointer.clone());
+        if let Some(mut input) = last_query.get_untracked() {
+            input.facet_type = facet_type;
+            input.query = pointer;
+            input.mode = None;
+            input.value_filter = None;
+            set_query.set(None);
+            query_model_action.dispatch(input);
         }
     });
-    let parsed_query = Memo::new(move |_| serde_json::from_str::<Value>(&query.get()).ok());
 
     view! {
         // Input
@@ -186,7 +492,10 @@ fn HomePage() -> impl IntoView {
                     name="model_id"
                     placeholder="Model Id"
                     size=40
-                    value="4fd3dccd-9b87-4fde-9b50-db4f57ab10e6"
+                    value=move || {
+                        let id = model_id.get();
+                        if id.is_empty() { DEFAULT_MODEL_ID.to_string() } else { id }
+                    }
                     class="flex-cmd-model-id"
                     on:input=move |_| set_model_versions.set(vec!["".to_string()])
                 />
@@ -206,7 +515,15 @@ fn HomePage() -> impl IntoView {
                             .into_iter()
                             .map(|v| {
                                 let value = v.clone();
-                                view! { <option value=value>{v}</option> }
+                                let is_selected = {
+                                    let v = v.clone();
+                                    move || selected_version.get() == v
+                                };
+                                view! {
+                                    <option value=value selected=is_selected>
+                                        {v}
+                                    </option>
+                                }
                             })
                             .collect_view()
                     }}
@@ -214,6 +531,9 @@ fn HomePage() -> impl IntoView {
                 <button type="submit" class="flex-cmd-item" on:click=move |_| set_rhs_mode.set(RHSMode::ModelStats)>
                     Read model
                 </button>
+                <button type="button" class="flex-cmd-item" on:click=move |_| set_rhs_mode.set(RHSMode::CacheAdmin)>
+                    Cache
+                </button>
             </div>
         </ActionForm>
 
@@ -233,8 +553,13 @@ fn HomePage() -> impl IntoView {
                                     version=selected_version
                                     types=element_type
                                     natures=element_nature
+                                    query_value=query_pointer
+                                    set_query_value=set_query_pointer
+                                    selected_facet_type=query_facet_type
+                                    set_selected_facet_type=set_query_facet_type
                                     set_query=set_query
                                     set_rhs_mode=set_rhs_mode
+                                    on_run_saved_query=run_saved_query
                                 />
                             </ActionForm>
                             <div class="staus-bar-flex-parent">
@@ -246,13 +571,31 @@ fn HomePage() -> impl IntoView {
                                 </div>
                             </div>
                             //TODO move the next set of result
-                            <json_viewer::JsonViewer
-                                json_value=parsed_query
-                                collapsed=false
-                                set_selected_object_id=set_selected_object_id
-                                set_rhs_mode=set_rhs_mode
-                            />
-                            <json_viewer::RenderStats json_value=parsed_query />
+                            {move || {
+                                if is_table_result.get() {
+                                    view! {
+                                        <table_viewer::TableViewer
+                                            table=element_table
+                                            set_selected_object_id=set_selected_object_id
+                                            set_rhs_mode=set_rhs_mode
+                                        />
+                                    }
+                                        .into_any()
+                                } else {
+                                    view! {
+                                        <json_viewer::JsonViewer
+                                            json_value=query
+                                            collapsed=false
+                                            set_selected_object_id=set_selected_object_id
+                                            set_rhs_mode=set_rhs_mode
+                                            result_query=result_query
+                                            on_query_path=query_path
+                                        />
+                                    }
+                                        .into_any()
+                                }
+                            }}
+                            <stats_viewer::RenderStats stats=query_stats />
                             <div>"Duration: " {duration}</div>
                         }
                             .into_any()
@@ -264,6 +607,78 @@ fn HomePage() -> impl IntoView {
 
             // RHS
             <div class="flex-container-rhs">
+                {move || {
+                    (!model_id.get().is_empty())
+                        .then(|| {
+                            view! {
+                                <div class="rhs-tabs">
+                                    <button
+                                        type="button"
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::ModelStats)
+                                    >
+                                        "Model Stats"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::FacetSchema)
+                                    >
+                                        "Facet Schema"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::Aggregation)
+                                    >
+                                        "Aggregation"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::Search)
+                                    >
+                                        "Search"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        disabled=move || selected_object_id.get().is_empty()
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::ElementDetail)
+                                    >
+                                        "Element"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        disabled=move || selected_object_id.get().is_empty()
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::Graph)
+                                    >
+                                        "Graph"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::Containment)
+                                    >
+                                        "Hierarchy"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::MetaGraph)
+                                    >
+                                        "Type Schema"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        disabled=move || selected_object_id.get().is_empty()
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::Impact)
+                                    >
+                                        "Impact"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        on:click=move |_| set_rhs_mode.set(RHSMode::Compare)
+                                    >
+                                        "Compare (" {move || pinned.with(|p| p.len())} ")"
+                                    </button>
+                                </div>
+                            }
+                        })
+                }}
                 {move || match rhs_mode.get() {
                     RHSMode::Rel(_) => {
                         log!("[RHS] Rending relationship viewer");
@@ -277,6 +692,133 @@ fn HomePage() -> impl IntoView {
                         }
                             .into_any()
                     }
+                    RHSMode::ValueBreakdown => {
+                        log!("[RHS] Rending value breakdown viewer");
+                        view! {
+                            <value_breakdown_viewer::ValueBreakdownViewer
+                                breakdown=value_breakdown
+                                on_filter=filter_by_value
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::FacetSchema => {
+                        log!("[RHS] Rending facet schema viewer");
+                        view! {
+                            <facet_schema_viewer::FacetSchemaViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::Aggregation => {
+                        log!("[RHS] Rending aggregation viewer");
+                        view! {
+                            <aggregation_viewer::AggregationViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                                last_query=last_query
+                                on_drill_down=show_group_elements
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::Search => {
+                        log!("[RHS] Rending search viewer");
+                        view! {
+                            <search_viewer::SearchViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                                on_show_element=show_element
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::ElementDetail => {
+                        log!("[RHS] Rending element detail viewer");
+                        view! {
+                            <element_detail_viewer::ElementDetailViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                                selected_object_id=selected_object_id
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                                on_pin=pin_element
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::Compare => {
+                        log!("[RHS] Rending compare viewer");
+                        view! {
+                            <compare_viewer::CompareViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                                model_versions=model_versions
+                                pinned=pinned
+                                set_pinned=set_pinned
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::Graph => {
+                        log!("[RHS] Rending graph viewer");
+                        view! {
+                            <graph_viewer::GraphViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                                selected_object_id=selected_object_id
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::Containment => {
+                        log!("[RHS] Rending containment viewer");
+                        view! {
+                            <containment_viewer::ContainmentViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                                selected_object_id=selected_object_id
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::MetaGraph => {
+                        log!("[RHS] Rending meta graph viewer");
+                        view! {
+                            <meta_graph_viewer::MetaGraphViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::Impact => {
+                        log!("[RHS] Rending impact viewer");
+                        view! {
+                            <impact_viewer::ImpactViewer
+                                model_id=model_id
+                                selected_version=selected_version
+                                selected_object_id=selected_object_id
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                            />
+                        }
+                            .into_any()
+                    }
+                    RHSMode::CacheAdmin => {
+                        log!("[RHS] Rending cache admin viewer");
+                        view! { <cache_admin_viewer::CacheAdminViewer /> }.into_any()
+                    }
                     _ => {
                         log!("[RHS] Rending model stats viewer");
                         view! {
@@ -296,6 +838,58 @@ fn HomePage() -> impl IntoView {
     }
 }
 
+// Filters of a query for a deep link, empty filters left out
+fn get_query_filters(query: &QueryModel) -> Vec<(String, String)> {
+    [
+        ("id", Some(query.id.clone())),
+        ("types", Some(query.types.clone())),
+        ("natures", Some(query.natures.clone())),
+        ("query", Some(query.query.clone())),
+        ("depth", Some(query.depth.to_string())),
+        ("limit", Some(query.limit.to_string())),
+        ("facet_type", Some(query.facet_type.clone())),
+        ("is_detail", query.is_detail.clone()),
+        ("mode", query.mode.clone()),
+        ("value_filter", query.value_filter.clone()),
+        ("columns", query.columns.clone()),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| {
+        value
+            .filter(|v| !v.is_empty())
+            .map(|v| (name.to_string(), v))
+    })
+    .collect()
+}
+
+// Query of a deep link, None when the link has no query filters
+fn get_link_query(link: &ViewLink) -> Option<QueryModel> {
+    if link.filters.is_empty() {
+        return None;
+    }
+    let filter = |name: &str| link.get_filter(name).map(str::to_string);
+
+    Some(QueryModel {
+        model_id: link.model_id.clone(),
+        vers_no: link.version.clone(),
+        id: filter("id").unwrap_or_default(),
+        types: filter("types").unwrap_or("All".to_string()),
+        natures: filter("natures").unwrap_or("All".to_string()),
+        query: filter("query").unwrap_or_default(),
+        depth: filter("depth")
+            .and_then(|d| d.parse().ok())
+            .unwrap_or(DEFAULT_QUERY_DEPTH),
+        limit: filter("limit")
+            .and_then(|l| l.parse().ok())
+            .unwrap_or(DEFAULT_QUERY_LIMIT),
+        facet_type: filter("facet_type").unwrap_or_default(),
+        is_detail: filter("is_detail"),
+        mode: filter("mode"),
+        value_filter: filter("value_filter"),
+        columns: filter("columns"),
+    })
+}
+
 /// 404 - Not Found
 #[component]
 fn NotFound() -> impl IntoView {
@@ -325,33 +919,17 @@ pub async fn parse_model(model_id: String, vers_no: String) -> Result<ServerResu
     use std::time::Instant;
     let start_time = Instant::now();
     use crate::model::app_state;
-    use actix_web::web::Data;
-    use leptos_actix::*;
 
     //Get app state
-    let app_state: Data<app_state::AppState> = extract().await?;
-    let pg_pool = app_state.get_pg_pool_ref();
-    let cache = app_state.get_model_cache();
+    let app_state = app_state::extract_app_state().await?;
 
     //Read all model version
-    let model_versions = parser::read_model_data_versions(&pg_pool, &model_id)
+    let model_versions = parser::read_model_data_versions(app_state.get_pg_pool_ref(), &model_id)
         .await
         .unwrap_or_default();
 
     // Read saved model
-    let version_num = vers_no
-        .parse::<i32>()
-        .unwrap_or_else(|_| model_versions.first().map_or(0, |v| v.vers_no));
-    let model_data = parser::read_model_data(&pg_pool, &cache, &model_id, version_num).await;
-    let model_data = match model_data {
-        Ok(model_data) => model_data,
-        Err(_) => {
-            eprintln!("Unable to find / read saved Model");
-            return Err(ServerFnError::ServerError(
-                "Unable to find / read saved Model".to_string(),
-            ));
-        }
-    };
+    let model_data = parser::read_model(&app_state, &model_id, &vers_no).await?;
 
     //Build stats
     let dict = model_dict::ModelDictionary::from(&model_data);
@@ -389,12 +967,16 @@ pub async fn query_model(
     limit: usize,
     facet_type: String,
     is_detail: Option<String>,
+    mode: Option<String>,
+    value_filter: Option<String>,
+    columns: Option<String>,
 ) -> Result<QueryResult, ServerFnError> {
     use crate::model::app_state;
+    use crate::model::element_table::TableColumn;
+    use crate::model::model_dict;
     use crate::model::parser;
-    use actix_web::web::Data;
+    use crate::model::value_stats::compute_numeric_stats;
     use leptos::logging::log;
-    use leptos_actix::*;
     use std::time::Instant;
 
     log!(
@@ -419,53 +1001,71 @@ pub async fn query_model(
 
     let start_time = Instant::now();
 
-    // Get DB pool
-    let app_state: Data<app_state::AppState> = extract().await?;
-    let pg_pool = app_state.get_pg_pool_ref();
-    let cache = app_state.get_model_cache();
-
     // Read saved model
-    let version_num = vers_no.parse::<i32>().unwrap_or_else(|_| 0);
-    let model_data = parser::read_model_data(pg_pool, &cache, &model_id, version_num).await;
-    let model_data = match model_data {
-        Ok(model_data) => model_data,
-        Err(_) => {
-            eprintln!("Unable to read saved Model");
-            return Err(ServerFnError::ServerError(
-                "Unable to read saved Model".to_string(),
-            ));
-        }
-    };
+    let app_state = app_state::extract_app_state().await?;
+    let model_data = parser::read_model(&app_state, &model_id, &vers_no).await?;
 
     //Filtering
-    //filter id
-    let mut filtered_elements = match id.is_empty() {
-        true => model_data.get_elements(),
-        false => match model_data.get_element_with_id(&id) {
-            Some(e) => vec![e],
-            None => vec![],
-        },
-    };
+    let mut filtered_elements = model_data.get_filtered_elements(&id, &types, &natures);
 
-    //filter nature
-    filtered_elements.retain(|e| match natures.as_str() {
-        "All" => true,
-        _ => *e.nature == natures,
-    });
+    //Apply json pointer
+    let facet_type: Option<FacetType> = FacetType::from_form_value(&facet_type);
 
-    //filter type
-    filtered_elements.retain(|e| match types.as_str() {
-        "All" => true,
-        _ => *e.type_ == types,
-    });
+    //filter value at pointer
+    if let (Some(value_filter), Some(facet_type)) = (value_filter, &facet_type) {
+        println!("[query_model] filtering value {} at {}", value_filter, &query);
+        filtered_elements
+            .retain(|e| model_dict::get_element_value_key(e, facet_type, &query) == value_filter);
+    }
 
-    //Apply json pointer
-    let facet_type: Option<FacetType> = match facet_type.as_str() {
-        "dynamicFacets" => Some(FacetType::DynamicFacets),
-        "coreFacets" => Some(FacetType::CoreFacets),
-        "facets" => Some(FacetType::Facets),
-        _ => None,
-    };
+    //Value breakdown
+    if mode.as_deref() == Some(VALUE_BREAKDOWN_MODE) {
+        let facet_type = match (&facet_type, query.is_empty()) {
+            (Some(facet_type), false) => facet_type,
+            _ => {
+                return Err(ServerFnError::ServerError(
+                    "Value breakdown requires a facet type and a JSON pointer".to_string(),
+                ))
+            }
+        };
+        let breakdown = model_dict::generate_value_breakdown(&filtered_elements, facet_type, &query);
+        let elapsed_time = start_time.elapsed();
+
+        return Ok(QueryResult {
+            data: serde_json::to_value(&breakdown.values).unwrap_or_default(),
+            duration: format!("Value breakdown took {} ms", elapsed_time.as_millis()),
+            result_count: breakdown.element_count - breakdown.missing_count,
+            total_result_count: breakdown.element_count,
+            stats: None,
+            breakdown: Some(breakdown),
+            table: None,
+        });
+    }
+
+    //Table of facet values
+    if mode.as_deref() == Some(TABLE_MODE) {
+        let columns = match columns.as_deref().filter(|c| !c.is_empty()) {
+            Some(columns) => TableColumn::from_json(columns),
+            None => Ok(vec![]),
+        };
+        let columns = match columns {
+            Ok(columns) => columns,
+            Err(e) => return Err(ServerFnError::ServerError(e)),
+        };
+        let limit = limit.min(filtered_elements.len());
+        let table = ElementTable::from_elements(&filtered_elements[0..limit], columns);
+        let elapsed_time = start_time.elapsed();
+
+        return Ok(QueryResult {
+            data: Value::Null,
+            duration: format!("Table query took {} ms", elapsed_time.as_millis()),
+            result_count: table.rows.len(),
+            total_result_count: filtered_elements.len(),
+            stats: None,
+            breakdown: None,
+            table: Some(table),
+        });
+    }
 
     let is_detail = is_detail.map_or(false, |v| v == "is_detail");
     println!(
@@ -483,6 +1083,9 @@ pub async fn query_model(
             .collect()
     };
 
+    //Stats of all filtered results
+    let stats = compute_numeric_stats(&filtered_elements);
+
     //Limit
     let limit = match limit >= filtered_elements.len() {
         true => filtered_elements.len(),
@@ -494,7 +1097,7 @@ pub async fn query_model(
         filtered_elements.len(),
         limit
     );
-    let limited_query_result = &filtered_elements[0..limit].to_vec();
+    let limited_query_result = &filtered_elements[0..limit];
 
     //Depth
     println!(
@@ -503,11 +1106,8 @@ pub async fn query_model(
         depth
     );
     let elements = match depth > 0 {
-        true => {
-            let filtered_element = cubs_model::truncate_value(&limited_query_result, depth);
-            serde_json::to_string_pretty(&filtered_element).unwrap()
-        }
-        false => serde_json::to_string_pretty(&limited_query_result).unwrap(),
+        true => cubs_model::truncate_value(limited_query_result, depth),
+        false => limited_query_result.to_vec(),
     };
     let elapsed_time = start_time.elapsed();
 
@@ -517,13 +1117,16 @@ pub async fn query_model(
     );
 
     Ok(QueryResult {
-        data: elements,
+        data: Value::Array(elements),
         duration: format!(
             "Query model took {} ms",
             elapsed_time.as_millis().to_string()
         ),
         result_count: limited_query_result.len(),
         total_result_count: filtered_elements.len(),
+        stats,
+        breakdown: None,
+        table: None,
     })
 }
 
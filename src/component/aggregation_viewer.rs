@@ -0,0 +1,255 @@
+use leptos::prelude::*;
+use serde_json::Value;
+
+use crate::app::QueryModel;
+use crate::model::aggregation::{AggregateSpec, AggregationResult, ElementFilter};
+use crate::model::element_table::TableColumn;
+
+#[component]
+pub fn AggregationViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    last_query: ReadSignal<Option<QueryModel>>,
+    on_drill_down: Callback<(Vec<Value>, usize)>,
+) -> impl IntoView {
+    let (group_by, set_group_by) = signal(String::new());
+    let (aggregates, set_aggregates) = signal("count".to_string());
+    let (error, set_error) = signal(None::<String>);
+
+    // Aggregate over the element filter of the last query
+    let element_filter = move || match last_query.get_untracked() {
+        Some(q) => (
+            q.model_id,
+            q.vers_no,
+            ElementFilter {
+                id: q.id,
+                types: q.types,
+                natures: q.natures,
+                facet_type: q.facet_type,
+                pointer: q.query,
+                value_filter: q.value_filter,
+            },
+        ),
+        None => (
+            model_id.get_untracked(),
+            selected_version.get_untracked(),
+            ElementFilter {
+                id: String::new(),
+                types: "All".to_string(),
+                natures: "All".to_string(),
+                facet_type: String::new(),
+                pointer: String::new(),
+                value_filter: None,
+            },
+        ),
+    };
+
+    // Filter of the last aggregation, its groups are drilled down with it
+    let aggregated_filter = StoredValue::new(None::<(String, String, ElementFilter)>);
+
+    // Typed lists are sent as json arrays
+    let aggregate_action = Action::new(move |(group_by, aggregates): &(String, String)| {
+        let (model_id, vers_no, filter) = element_filter();
+        aggregated_filter.set_value(Some((model_id.clone(), vers_no.clone(), filter.clone())));
+        let (group_by, aggregates) = (group_by.clone(), aggregates.clone());
+        async move { aggregate_model(model_id, vers_no, filter, group_by, aggregates).await }
+    });
+    let run_aggregation = move || {
+        let group_by = TableColumn::parse_list(&group_by.get_untracked());
+        let aggregates = AggregateSpec::parse_list(&aggregates.get_untracked());
+        match (group_by, aggregates) {
+            (Ok(group_by), Ok(aggregates)) => {
+                set_error.set(None);
+                aggregate_action.dispatch((
+                    serde_json::to_string(&group_by).unwrap_or_default(),
+                    serde_json::to_string(&aggregates).unwrap_or_default(),
+                ));
+            }
+            (Err(e), _) | (_, Err(e)) => set_error.set(Some(e)),
+        }
+    };
+    let result = aggregate_action.value();
+
+    let drill_down_action = Action::new(move |keys: &Vec<String>| {
+        let (model_id, vers_no, filter) = aggregated_filter.get_value().unwrap_or_else(element_filter);
+        let group_by = result.with_untracked(|r| {
+            r.as_ref()
+                .and_then(|r| r.as_ref().ok())
+                .map(|r| serde_json::to_string(&r.group_by).unwrap_or_default())
+                .unwrap_or_default()
+        });
+        let group_keys = serde_json::to_string(keys).unwrap_or_default();
+        async move { get_group_elements(model_id, vers_no, filter, group_by, group_keys).await }
+    });
+
+    Effect::new(move |_| match drill_down_action.value().get() {
+        Some(Ok(elements)) => on_drill_down.run(elements),
+        Some(Err(e)) => set_error.set(Some(e.to_string())),
+        None => {}
+    });
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Aggregation</h2>
+            <div class="aggregation-input">
+                <label>"Group by"</label>
+                <input
+                    type="text"
+                    placeholder="facets:/floor, coreFacets:/type, \\, inside a pointer"
+                    prop:value=group_by
+                    on:input=move |ev| set_group_by.set(event_target_value(&ev))
+                />
+                <label>"Aggregates"</label>
+                <input
+                    type="text"
+                    placeholder="count, sum(facets:/area), min(...), max(...), mean(...)"
+                    prop:value=aggregates
+                    on:input=move |ev| set_aggregates.set(event_target_value(&ev))
+                />
+                <button type="button" on:click=move |_| run_aggregation()>
+                    "Aggregate"
+                </button>
+            </div>
+            {move || error.get().map(|e| view! { <span class="json-error">{e}</span> })}
+            {move || match result.get() {
+                Some(Ok(result)) => {
+                    view! {
+                        <AggregationTable
+                            result=result
+                            on_select=Callback::new(move |keys: Vec<String>| {
+                                drill_down_action.dispatch(keys);
+                            })
+                        />
+                    }
+                        .into_any()
+                }
+                Some(Err(e)) => {
+                    view! { <span class="json-error">{format!("Error: {}", e)}</span> }.into_any()
+                }
+                None => {
+                    view! { <span>"Group elements of the current filter by facet values"</span> }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn AggregationTable(result: AggregationResult, on_select: Callback<Vec<String>>) -> impl IntoView {
+    view! {
+        <h4 class="label-model-stats">
+            "Elements: " {result.element_count} " Groups: " {result.groups.len()}
+        </h4>
+        <div class="aggregation-table">
+            <table>
+                <tr>
+                    {result
+                        .group_by
+                        .iter()
+                        .map(|c| view! { <th>{c.form_value()}</th> })
+                        .collect_view()}
+                    <th>"Elements"</th>
+                    {result
+                        .aggregates
+                        .iter()
+                        .map(|a| view! { <th>{a.label()}</th> })
+                        .collect_view()}
+                </tr>
+                {result
+                    .groups
+                    .into_iter()
+                    .map(|group| {
+                        let keys = group.keys.clone();
+                        view! {
+                            <tr
+                                class="value-breakdown-row"
+                                title="Show the elements of this group"
+                                on:click=move |_| on_select.run(keys.clone())
+                            >
+                                {group
+                                    .labels
+                                    .into_iter()
+                                    .map(|label| view! { <td>{label}</td> })
+                                    .collect_view()}
+                                <td>{group.element_count}</td>
+                                {group
+                                    .values
+                                    .into_iter()
+                                    .map(|v| {
+                                        view! { <td>{v.map(|v| v.to_string()).unwrap_or_default()}</td> }
+                                    })
+                                    .collect_view()}
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+            </table>
+        </div>
+    }
+}
+
+#[server(AggregateModel, "/api")]
+pub async fn aggregate_model(
+    model_id: String,
+    vers_no: String,
+    filter: ElementFilter,
+    group_by: String,
+    aggregates: String,
+) -> Result<AggregationResult, ServerFnError> {
+    use crate::model::aggregation::aggregate_elements;
+    use crate::model::{app_state, parser};
+    use std::time::Instant;
+
+    let start_time = Instant::now();
+    let group_by = TableColumn::from_json(&group_by).map_err(ServerFnError::new)?;
+    let aggregates = AggregateSpec::from_json(&aggregates).map_err(ServerFnError::new)?;
+    if group_by.is_empty() {
+        return Err(ServerFnError::new("Aggregation requires a group by column"));
+    }
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &vers_no).await?;
+    let elements = filter.get_elements(&model);
+    let result = aggregate_elements(&elements, group_by, aggregates);
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!("[Execution time] aggregate_model - {:?}", elapsed_time);
+
+    Ok(result)
+}
+
+// Elements of the group with the given value keys, and the number of elements in the group
+#[server(GetGroupElements, "/api")]
+pub async fn get_group_elements(
+    model_id: String,
+    vers_no: String,
+    filter: ElementFilter,
+    group_by: String,
+    group_keys: String,
+) -> Result<(Vec<Value>, usize), ServerFnError> {
+    use crate::model::aggregation::get_group_keys;
+    use crate::model::{app_state, parser};
+
+    // Limit of the elements shown when drilling down into a group
+    const DRILL_DOWN_LIMIT: usize = 1000;
+
+    let group_by = TableColumn::from_json(&group_by).map_err(ServerFnError::new)?;
+    let group_keys: Vec<String> = serde_json::from_str(&group_keys)?;
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &vers_no).await?;
+    let mut elements = filter.get_elements(&model);
+    elements.retain(|e| get_group_keys(e, &group_by) == group_keys);
+    let element_count = elements.len();
+
+    // Only the elements shown are serialized
+    let elements = elements
+        .into_iter()
+        .take(DRILL_DOWN_LIMIT)
+        .filter_map(|e| serde_json::to_value(e).ok())
+        .collect();
+
+    Ok((elements, element_count))
+}
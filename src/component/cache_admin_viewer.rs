@@ -0,0 +1,126 @@
+use leptos::prelude::*;
+
+use crate::model::cache_info::CacheEntryInfo;
+
+#[component]
+pub fn CacheAdminViewer() -> impl IntoView {
+    let evict_action = ServerAction::<EvictCacheEntry>::new();
+    let flush_action = ServerAction::<FlushCache>::new();
+
+    // Refetch the entries after every evict / flush
+    let cache_entries = Resource::new(
+        move || (evict_action.version().get(), flush_action.version().get()),
+        |_| async move { get_cache_entries().await },
+    );
+
+    let cache_entries_result = move || match cache_entries.get() {
+        Some(Ok(entries)) if entries.is_empty() => {
+            view! { <span class="json-error">"Cache is empty"</span> }.into_any()
+        }
+        Some(Ok(entries)) => {
+            let total_size: u64 = entries.iter().map(|e| e.size_bytes).sum();
+            view! {
+                <div class="table-model-stats">
+                    <table>
+                        <tr>
+                            <th>"Cache"</th>
+                            <th>"Model Id"</th>
+                            <th>"Version"</th>
+                            <th>"Size"</th>
+                            <th>"Age"</th>
+                            <th></th>
+                        </tr>
+                        {entries
+                            .into_iter()
+                            .map(|e| {
+                                let model_id = e.model_id.clone();
+                                let version = e.version.clone();
+                                view! {
+                                    <tr>
+                                        <td>{e.cache}</td>
+                                        <td>{e.model_id}</td>
+                                        <td>{e.version}</td>
+                                        <td>{format_bytes(e.size_bytes)}</td>
+                                        <td>{format!("{}s", e.age_secs)}</td>
+                                        <td>
+                                            <button on:click=move |_| {
+                                                evict_action
+                                                    .dispatch(EvictCacheEntry {
+                                                        model_id: model_id.clone(),
+                                                        version: version.clone(),
+                                                    });
+                                            }>"Evict"</button>
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                            .collect_view()}
+                    </table>
+                </div>
+                <h4 class="label-model-stats">"Total: " {format_bytes(total_size)}</h4>
+            }
+            .into_any()
+        }
+        Some(Err(e)) => view! { <span class="json-error">{e.to_string()}</span> }.into_any(),
+        None => view! { <span>"Loading ... "</span> }.into_any(),
+    };
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Cache</h2>
+            <button on:click=move |_| {
+                flush_action.dispatch(FlushCache {});
+            }>"Flush all"</button>
+            <Transition fallback=move || view! { <p>"Loading cache entries..."</p> }>
+                {cache_entries_result}
+            </Transition>
+        </div>
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[server(GetCacheEntries, "/api")]
+pub async fn get_cache_entries() -> Result<Vec<CacheEntryInfo>, ServerFnError> {
+    use crate::model::app_state;
+    use actix_web::web::Data;
+    use leptos_actix::*;
+
+    let app_state: Data<app_state::AppState> = extract().await?;
+    Ok(app_state.list_cache_entries())
+}
+
+#[server(EvictCacheEntry, "/api")]
+pub async fn evict_cache_entry(model_id: String, version: String) -> Result<bool, ServerFnError> {
+    use crate::model::app_state;
+    use actix_web::web::Data;
+    use leptos_actix::*;
+
+    println!(
+        "[CacheAdminViewer] evicting model: {} version: {}",
+        model_id, version
+    );
+    let app_state: Data<app_state::AppState> = extract().await?;
+    Ok(app_state.evict_cache_entry(&model_id, &version))
+}
+
+#[server(FlushCache, "/api")]
+pub async fn flush_cache() -> Result<(), ServerFnError> {
+    use crate::model::app_state;
+    use actix_web::web::Data;
+    use leptos_actix::*;
+
+    println!("[CacheAdminViewer] flushing all cache");
+    let app_state: Data<app_state::AppState> = extract().await?;
+    app_state.flush_cache();
+    Ok(())
+}
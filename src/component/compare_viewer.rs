@@ -0,0 +1,281 @@
+use leptos::prelude::*;
+use serde_json::Value;
+
+use crate::app::RHSMode;
+use crate::model::element_diff::{ElementComparison, FacetGroupDiff, PinnedElement};
+use crate::model::model_error::ModelError;
+
+// Pinned elements compared at once
+pub const MAX_PINNED_ELEMENTS: usize = 6;
+
+#[component]
+pub fn CompareViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    model_versions: ReadSignal<Vec<String>>,
+    pinned: ReadSignal<Vec<PinnedElement>>,
+    set_pinned: WriteSignal<Vec<PinnedElement>>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let (pin_id, set_pin_id) = signal(String::new());
+    let (pin_version, set_pin_version) = signal(selected_version.get_untracked());
+    let (is_only_differences, set_only_differences) = signal(true);
+
+    let async_comparison = Resource::new(
+        move || pinned.get(),
+        |pinned| async move {
+            if pinned.len() < 2 {
+                return Ok(ElementComparison::default());
+            }
+            compare_pinned_elements(pinned).await
+        },
+    );
+
+    let pin = move |_| {
+        let id = pin_id.get_untracked().trim().to_string();
+        if id.is_empty() {
+            return;
+        }
+        let element = PinnedElement {
+            model_id: model_id.get_untracked(),
+            version: pin_version.get_untracked(),
+            id,
+        };
+        set_pinned.update(|p| {
+            if !p.contains(&element) && p.len() < MAX_PINNED_ELEMENTS {
+                p.push(element);
+            }
+        });
+        set_pin_id.set(String::new());
+    };
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Compare Elements</h2>
+            <div class="compare-viewer-input">
+                <input
+                    type="text"
+                    placeholder="Element id"
+                    prop:value=pin_id
+                    on:input=move |ev| set_pin_id.set(event_target_value(&ev))
+                />
+                <select on:change=move |ev| set_pin_version.set(event_target_value(&ev))>
+                    {move || {
+                        model_versions
+                            .get()
+                            .into_iter()
+                            .map(|v| {
+                                let value = v.clone();
+                                let is_selected = {
+                                    let v = v.clone();
+                                    move || pin_version.get() == v
+                                };
+                                view! {
+                                    <option value=value selected=is_selected>
+                                        {v}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+                <button type="button" on:click=pin>
+                    "Pin"
+                </button>
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=is_only_differences
+                        on:change=move |ev| set_only_differences.set(event_target_checked(&ev))
+                    />
+                    "Only differences"
+                </label>
+            </div>
+            <div>
+                {move || {
+                    pinned
+                        .get()
+                        .into_iter()
+                        .map(|element| {
+                            let label = format!("{} @ v{}", element.id, element.version);
+                            view! {
+                                <span class="table-column-chip">
+                                    {label}
+                                    <button
+                                        type="button"
+                                        on:click=move |_| {
+                                            set_pinned.update(|p| p.retain(|e| *e != element))
+                                        }
+                                    >
+                                        "×"
+                                    </button>
+                                </span>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </div>
+            <Transition fallback=move || view! { <p>"Comparing..."</p> }>
+                {move || match async_comparison.get() {
+                    Some(Ok(comparison)) if comparison.elements.len() >= 2 => {
+                        view! {
+                            <ComparisonRender
+                                comparison=comparison
+                                is_only_differences=is_only_differences
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                            />
+                        }
+                            .into_any()
+                    }
+                    Some(Ok(_)) => {
+                        view! {
+                            <span>"Pin two or more elements from the element detail or by id"</span>
+                        }
+                            .into_any()
+                    }
+                    Some(Err(e)) => {
+                        view! { <span class="json-error">{format!("Error: {}", e)}</span> }
+                            .into_any()
+                    }
+                    None => view! { <span>"Loading ... "</span> }.into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn ComparisonRender(
+    comparison: ElementComparison,
+    is_only_differences: ReadSignal<bool>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let difference_count = comparison.get_difference_count();
+    let headers = comparison
+        .elements
+        .iter()
+        .map(|element| {
+            let id = element.pinned.id.clone();
+            let label = match element.is_found {
+                true => format!(
+                    "{} : {} (v{})",
+                    element.name, element.type_, element.pinned.version
+                ),
+                false => format!(
+                    "{} not found in v{}",
+                    element.pinned.id, element.pinned.version
+                ),
+            };
+            view! {
+                <th
+                    class="table-viewer-id"
+                    title="Show the detail of this element"
+                    on:click=move |_| {
+                        set_selected_object_id.set(id.clone());
+                        set_rhs_mode.set(RHSMode::ElementDetail);
+                    }
+                >
+                    {label}
+                </th>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <h4 class="label-model-stats">{difference_count} " differences"</h4>
+        <div class="compare-viewer">
+            <table>
+                <tr>
+                    <th>"Pointer"</th>
+                    {headers}
+                </tr>
+                {comparison
+                    .groups
+                    .into_iter()
+                    .map(|group| view! { <FacetGroupDiffRender group=group is_only_differences=is_only_differences /> })
+                    .collect_view()}
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn FacetGroupDiffRender(
+    group: FacetGroupDiff,
+    is_only_differences: ReadSignal<bool>,
+) -> impl IntoView {
+    let column_count = group.rows.first().map_or(0, |r| r.values.len()) + 1;
+
+    view! {
+        <tr class="compare-viewer-group">
+            <td colspan=column_count>{group.facet_type}</td>
+        </tr>
+        {group
+            .rows
+            .into_iter()
+            .map(|row| {
+                let is_different = row.is_different;
+                view! {
+                    <tr
+                        class:compare-viewer-different=is_different
+                        style:display=move || {
+                            if is_only_differences.get() && !is_different { "none" } else { "" }
+                        }
+                    >
+                        <td>{row.pointer}</td>
+                        {row
+                            .values
+                            .into_iter()
+                            .map(|value| view! { <td>{get_cell_text(value)}</td> })
+                            .collect_view()}
+                    </tr>
+                }
+            })
+            .collect_view()}
+    }
+}
+
+fn get_cell_text(value: Option<Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s,
+        Some(value) => value.to_string(),
+        None => "—".to_string(),
+    }
+}
+
+#[server(ComparePinnedElements, "/api")]
+pub async fn compare_pinned_elements(
+    pinned: Vec<PinnedElement>,
+) -> Result<ElementComparison, ModelError> {
+    use crate::model::{app_state, parser};
+    use std::time::Instant;
+
+    if pinned.len() < 2 || pinned.len() > MAX_PINNED_ELEMENTS {
+        return Err(ModelError::InvalidInput);
+    }
+
+    let start_time = Instant::now();
+    let app_state = app_state::extract_app_state().await?;
+    let mut models = Vec::with_capacity(pinned.len());
+    for element in &pinned {
+        models.push(parser::read_model(&app_state, &element.model_id, &element.version).await?);
+    }
+    let elements: Vec<_> = pinned
+        .iter()
+        .zip(&models)
+        .map(|(element, model)| model.get_element_with_id(&element.id))
+        .collect();
+    let comparison = ElementComparison::from(pinned.clone(), &elements);
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!(
+        "[Execution time] compare_pinned_elements - {:?}",
+        elapsed_time
+    );
+
+    Ok(comparison)
+}
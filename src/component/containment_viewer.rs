@@ -0,0 +1,444 @@
+use std::collections::{HashMap, HashSet};
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::app::RHSMode;
+use crate::model::containment::{ContainmentConfig, ContainmentEntry};
+use crate::model::model_error::ModelError;
+
+// Level key of the roots
+const ROOT_KEY: &str = "";
+
+// Signals shared by every level of the tree
+#[derive(Clone, Copy)]
+struct ContainmentState {
+    // Loaded children by container id
+    levels: RwSignal<HashMap<String, Vec<ContainmentEntry>>>,
+    expanded: RwSignal<HashSet<String>>,
+    load_level: Callback<String>,
+    selected_object_id: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
+}
+
+#[component]
+pub fn ContainmentViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    selected_object_id: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let (types, set_types) = signal(String::new());
+    let (natures, set_natures) = signal(String::new());
+    // Config the tree was loaded with, None until the server default arrives
+    let (config, set_config) = signal(None::<ContainmentConfig>);
+    let (error, set_error) = signal(None::<String>);
+    let levels = RwSignal::new(HashMap::<String, Vec<ContainmentEntry>>::new());
+    let expanded = RwSignal::new(HashSet::<String>::new());
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match get_default_containment_config().await {
+                Ok(default) => {
+                    set_types.set(default.relationship_types.join(", "));
+                    set_natures.set(default.relationship_natures.join(", "));
+                    set_config.set(Some(default));
+                }
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+        });
+    });
+
+    let load_level = Callback::new(move |parent_id: String| {
+        let Some(current_config) = config.get_untracked() else {
+            return;
+        };
+        if levels.with_untracked(|l| l.contains_key(&parent_id)) {
+            return;
+        }
+        let (model, version) = (model_id.get_untracked(), selected_version.get_untracked());
+        spawn_local(async move {
+            let parent = (parent_id != ROOT_KEY).then(|| parent_id.clone());
+            let result = get_containment_level(
+                model.clone(),
+                version.clone(),
+                current_config.clone(),
+                parent,
+            )
+            .await;
+            // Drop levels of a model version or config no longer selected
+            if model != model_id.get_untracked()
+                || version != selected_version.get_untracked()
+                || config.get_untracked() != Some(current_config)
+            {
+                return;
+            }
+            match result {
+                Ok(entries) => {
+                    set_error.set(None);
+                    levels.update(|l| {
+                        l.insert(parent_id, entries);
+                    });
+                }
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+        });
+    });
+
+    // Reload from the roots when the model version or the config changes
+    Effect::new(move |_| {
+        model_id.track();
+        selected_version.track();
+        config.track();
+        levels.update(|l| l.clear());
+        expanded.update(|e| e.clear());
+        load_level.run(ROOT_KEY.to_string());
+    });
+
+    let async_breadcrumbs = Resource::new(
+        move || {
+            (
+                model_id.get(),
+                selected_version.get(),
+                config.get(),
+                selected_object_id.get(),
+            )
+        },
+        |(model_id, version, config, id)| async move {
+            match config {
+                Some(config) if !id.is_empty() => {
+                    get_containment_breadcrumbs(model_id, version, config, id).await
+                }
+                _ => Ok(Vec::new()),
+            }
+        },
+    );
+
+    // Reveal the selected element by expanding its containers
+    Effect::new(move |_| {
+        if let Some(Ok(breadcrumbs)) = async_breadcrumbs.get() {
+            let containers = breadcrumbs.iter().take(breadcrumbs.len().saturating_sub(1));
+            for entry in containers {
+                load_level.run(entry.id.clone());
+                expanded.update(|e| {
+                    e.insert(entry.id.clone());
+                });
+            }
+        }
+    });
+
+    let state = ContainmentState {
+        levels,
+        expanded,
+        load_level,
+        selected_object_id,
+        set_selected_object_id,
+    };
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Containment Hierarchy</h2>
+            <div class="containment-viewer-input">
+                <input
+                    type="text"
+                    placeholder="Relationship types, comma separated"
+                    prop:value=types
+                    on:input=move |ev| set_types.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder="Relationship natures, comma separated"
+                    prop:value=natures
+                    on:input=move |ev| set_natures.set(event_target_value(&ev))
+                />
+                <button
+                    type="button"
+                    on:click=move |_| {
+                        let new_config = ContainmentConfig::from_form(
+                            &types.get_untracked(),
+                            &natures.get_untracked(),
+                        );
+                        match new_config.validate() {
+                            Ok(_) => set_config.set(Some(new_config)),
+                            Err(e) => set_error.set(Some(e)),
+                        }
+                    }
+                >
+                    "Apply"
+                </button>
+            </div>
+            {move || error.get().map(|e| view! { <span class="json-error">{format!("Error: {}", e)}</span> })}
+
+            <div class="containment-viewer-breadcrumbs">
+                <Transition>
+                    {move || {
+                        async_breadcrumbs
+                            .get()
+                            .and_then(|r| r.ok())
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|entry| {
+                                let id = entry.id.clone();
+                                let name = match entry.name.is_empty() {
+                                    true => entry.id,
+                                    false => entry.name,
+                                };
+                                view! {
+                                    <span
+                                        class="containment-viewer-crumb"
+                                        title=entry.type_
+                                        on:click=move |_| set_selected_object_id.set(id.clone())
+                                    >
+                                        {name}
+                                    </span>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </Transition>
+                <button
+                    type="button"
+                    disabled=move || selected_object_id.get().is_empty()
+                    on:click=move |_| set_rhs_mode.set(RHSMode::ElementDetail)
+                >
+                    "Detail"
+                </button>
+            </div>
+
+            <ContainmentLevel state=state parent_id=ROOT_KEY.to_string() />
+        </div>
+    }
+}
+
+// Contained elements of a container, roots for the root key
+#[component]
+fn ContainmentLevel(state: ContainmentState, parent_id: String) -> AnyView {
+    let level = Memo::new(move |_| state.levels.with(|l| l.get(&parent_id).cloned()));
+
+    view! {
+        {move || match level.get() {
+            Some(entries) if entries.is_empty() => {
+                view! { <div class="relationship-tree-empty">"No containment relationship"</div> }
+                    .into_any()
+            }
+            Some(entries) => {
+                view! {
+                    <ul class="relationship-tree">
+                        {entries
+                            .into_iter()
+                            .map(|entry| view! { <ContainmentEntryRender state=state entry=entry /> })
+                            .collect_view()}
+                    </ul>
+                }
+                    .into_any()
+            }
+            None => view! { <div class="relationship-tree-empty">"Loading ... "</div> }.into_any(),
+        }}
+    }
+    .into_any()
+}
+
+#[component]
+fn ContainmentEntryRender(state: ContainmentState, entry: ContainmentEntry) -> impl IntoView {
+    let id = entry.id.clone();
+    let can_expand = entry.child_count > 0;
+    let is_expanded = {
+        let id = id.clone();
+        Memo::new(move |_| state.expanded.with(|e| e.contains(&id)))
+    };
+    let is_selected = {
+        let id = id.clone();
+        move || state.selected_object_id.get() == id
+    };
+    let toggle = {
+        let id = id.clone();
+        move |_| {
+            if is_expanded.get_untracked() {
+                state.expanded.update(|e| {
+                    e.remove(&id);
+                });
+            } else {
+                state.load_level.run(id.clone());
+                state.expanded.update(|e| {
+                    e.insert(id.clone());
+                });
+            }
+        }
+    };
+    let select_id = id.clone();
+    let name = match entry.name.is_empty() {
+        true => entry.id.clone(),
+        false => entry.name,
+    };
+
+    view! {
+        <li>
+            <div class="relationship-tree-row">
+                <button
+                    type="button"
+                    class="relationship-tree-toggle"
+                    disabled=!can_expand
+                    on:click=toggle
+                >
+                    {move || match (can_expand, is_expanded.get()) {
+                        (false, _) => "•",
+                        (true, true) => "▾",
+                        (true, false) => "▸",
+                    }}
+                </button>
+                <span
+                    class="relationship-tree-element"
+                    class:containment-viewer-selected=is_selected
+                    on:click=move |_| state.set_selected_object_id.set(select_id.clone())
+                >
+                    {name}
+                    " : "
+                    {entry.type_}
+                </span>
+                {can_expand
+                    .then(|| {
+                        view! {
+                            <span
+                                class="relationship-tree-count"
+                                title="Directly contained / all contained elements"
+                            >
+                                "(" {entry.child_count} " / " {entry.subtree_count} ")"
+                            </span>
+                        }
+                    })}
+            </div>
+            {move || {
+                is_expanded
+                    .get()
+                    .then(|| view! { <ContainmentLevel state=state parent_id=id.clone() /> })
+            }}
+        </li>
+    }
+}
+
+// Containment forest of a model version, only the relationships matching the config.
+// Cached per model version and config as each level and breadcrumb request needs it
+#[cfg(feature = "ssr")]
+async fn read_containment_forest(
+    model_id: &str,
+    version: &str,
+    config: &ContainmentConfig,
+) -> Result<
+    (
+        std::sync::Arc<crate::model::cubs_model::ModelData>,
+        std::sync::Arc<crate::model::containment::ContainmentForest>,
+    ),
+    ModelError,
+> {
+    use crate::model::containment::ContainmentForest;
+    use crate::model::{app_state, parser};
+    use std::sync::Arc;
+
+    if model_id.is_empty() || config.validate().is_err() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, model_id, version).await?;
+    let forest_version = app_state::variant_version(&model.version.to_string(), &config.get_key());
+    let forest = app_state
+        .get_containment_cache()
+        .get_or_load(model_id, &forest_version, async {
+            let graph = parser::read_model_graph(&app_state, model_id, &model).await?;
+
+            // Sorted so the container kept for elements with several is stable
+            let mut edges: Vec<(&str, &str)> = graph
+                .get_connectors()
+                .into_iter()
+                .flat_map(|connector| {
+                    let model = &model;
+                    connector.get_out_id().into_iter().filter_map(move |path| {
+                        let relationship = model.get_relationship_with_id(&path.0)?;
+                        config
+                            .is_containment(&relationship.type_, &relationship.nature)
+                            .then_some((connector.get_element_id(), path.1.as_str()))
+                    })
+                })
+                .collect();
+            edges.sort();
+            Ok::<_, ModelError>(Arc::new(ContainmentForest::from(&edges)))
+        })
+        .await?;
+
+    Ok((model, forest))
+}
+
+#[cfg(feature = "ssr")]
+fn to_entries(
+    ids: &[String],
+    model: &crate::model::cubs_model::ModelData,
+    forest: &crate::model::containment::ContainmentForest,
+) -> Vec<ContainmentEntry> {
+    ids.iter()
+        .map(|id| {
+            let element = model.get_element_with_id(id);
+            ContainmentEntry {
+                id: id.clone(),
+                name: element.map(|e| e.name.clone()).unwrap_or_default(),
+                type_: element.map(|e| e.type_.clone()).unwrap_or_default(),
+                child_count: forest.get_children(id).len(),
+                subtree_count: forest.get_subtree_count(id),
+            }
+        })
+        .collect()
+}
+
+#[server(GetDefaultContainmentConfig, "/api")]
+pub async fn get_default_containment_config() -> Result<ContainmentConfig, ServerFnError> {
+    // Containment relationships unless set in the environment
+    const DEFAULT_CONTAINMENT_TYPES: &str = "contains";
+
+    let types = std::env::var("CONTAINMENT_RELATIONSHIP_TYPES")
+        .unwrap_or_else(|_| DEFAULT_CONTAINMENT_TYPES.to_string());
+    let natures = std::env::var("CONTAINMENT_RELATIONSHIP_NATURES").unwrap_or_default();
+    Ok(ContainmentConfig::from_form(&types, &natures))
+}
+
+#[server(GetContainmentLevel, "/api")]
+pub async fn get_containment_level(
+    model_id: String,
+    version: String,
+    config: ContainmentConfig,
+    parent_id: Option<String>,
+) -> Result<Vec<ContainmentEntry>, ModelError> {
+    use std::time::Instant;
+
+    println!(
+        "[ContainmentViewer] get_containment_level for model: {}, parent: {:?}",
+        model_id, parent_id
+    );
+    let start_time = Instant::now();
+
+    let (model, forest) = read_containment_forest(&model_id, &version, &config).await?;
+    let ids = match &parent_id {
+        Some(parent_id) => forest.get_children(parent_id),
+        None => forest.get_roots(),
+    };
+    let entries = to_entries(ids, &model, &forest);
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!(
+        "[Execution time] get_containment_level - {:?}",
+        elapsed_time
+    );
+
+    Ok(entries)
+}
+
+#[server(GetContainmentBreadcrumbs, "/api")]
+pub async fn get_containment_breadcrumbs(
+    model_id: String,
+    version: String,
+    config: ContainmentConfig,
+    id: String,
+) -> Result<Vec<ContainmentEntry>, ModelError> {
+    let (model, forest) = read_containment_forest(&model_id, &version, &config).await?;
+    Ok(to_entries(&forest.get_breadcrumbs(&id), &model, &forest))
+}
@@ -0,0 +1,250 @@
+use leptos::prelude::*;
+use serde_json::Value;
+
+use crate::app::{QueryModel, RHSMode};
+use crate::component::json_viewer::JsonViewer;
+use crate::model::element_detail::{ElementDetail, RelationshipRow};
+use crate::model::model_error::ModelError;
+
+#[component]
+pub fn ElementDetailViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    selected_object_id: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+    on_pin: Callback<String>,
+) -> impl IntoView {
+    let async_detail = Resource::new(
+        move || {
+            (
+                model_id.get(),
+                selected_version.get(),
+                selected_object_id.get(),
+            )
+        },
+        |(model_id, version, id)| async move { get_element_detail(model_id, version, id).await },
+    );
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Element Detail</h2>
+            <Transition fallback=move || view! { <p>"Loading element..."</p> }>
+                {move || match async_detail.get() {
+                    Some(Ok(detail)) => {
+                        view! {
+                            <ElementDetailRender
+                                detail=detail
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                                on_pin=on_pin
+                            />
+                        }
+                            .into_any()
+                    }
+                    Some(Err(e)) => {
+                        view! { <span class="json-error">{format!("Error: {}", e)}</span> }
+                            .into_any()
+                    }
+                    None => view! { <span>"Loading ... "</span> }.into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn ElementDetailRender(
+    detail: ElementDetail,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+    on_pin: Callback<String>,
+) -> impl IntoView {
+    let id = detail.id.clone();
+    let pin_id = detail.id.clone();
+    let facet_groups = [
+        ("Core Facets", detail.core_facets),
+        ("Facets", detail.facets),
+        ("Dynamic Facets", detail.dynamic_facets),
+    ];
+
+    view! {
+        <table class="element-detail-fields">
+            <tr>
+                <td>"Id"</td>
+                <td>{detail.id}</td>
+            </tr>
+            <tr>
+                <td>"Name"</td>
+                <td>{detail.name}</td>
+            </tr>
+            <tr>
+                <td>"Type"</td>
+                <td>{detail.type_}</td>
+            </tr>
+            <tr>
+                <td>"Nature"</td>
+                <td>{detail.nature}</td>
+            </tr>
+            <tr>
+                <td>"Version"</td>
+                <td>{detail.version}</td>
+            </tr>
+        </table>
+        <button
+            type="button"
+            on:click=move |_| set_rhs_mode.set(RHSMode::Rel(id.clone()))
+        >
+            "Relationship tree"
+        </button>
+        <button type="button" on:click=move |_| on_pin.run(pin_id.clone())>
+            "Pin for comparison"
+        </button>
+
+        {facet_groups
+            .into_iter()
+            .map(|(label, value)| {
+                view! {
+                    <FacetGroupRender
+                        label=label
+                        value=value
+                        set_selected_object_id=set_selected_object_id
+                        set_rhs_mode=set_rhs_mode
+                    />
+                }
+            })
+            .collect_view()}
+
+        <h3 class="label-model-stats">"Incoming (" {detail.incoming.len()} ")"</h3>
+        <RelationshipTable rows=detail.incoming set_selected_object_id=set_selected_object_id />
+        <h3 class="label-model-stats">"Outgoing (" {detail.outgoing.len()} ")"</h3>
+        <RelationshipTable rows=detail.outgoing set_selected_object_id=set_selected_object_id />
+    }
+}
+
+#[component]
+fn FacetGroupRender(
+    label: &'static str,
+    value: Value,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let key_count = value.as_object().map_or(0, |v| v.len());
+    let (json_value, _) = signal(Some(value));
+    // Facet trees are not query results, no pointer to resolve
+    let (result_query, _) = signal(None::<QueryModel>);
+
+    view! {
+        <details class="element-detail-facets">
+            <summary>{label} " (" {key_count} ")"</summary>
+            <JsonViewer
+                json_value=json_value
+                collapsed=true
+                set_selected_object_id=set_selected_object_id
+                set_rhs_mode=set_rhs_mode
+                result_query=result_query
+                on_query_path=Callback::new(|_| {})
+            />
+        </details>
+    }
+}
+
+#[component]
+fn RelationshipTable(
+    rows: Vec<RelationshipRow>,
+    set_selected_object_id: WriteSignal<String>,
+) -> impl IntoView {
+    view! {
+        <table class="element-detail-relationships">
+            <tr>
+                <th>"Relationship"</th>
+                <th>"Name"</th>
+                <th>"Type"</th>
+            </tr>
+            {rows
+                .into_iter()
+                .map(|row| {
+                    let counterpart_id = row.counterpart_id.clone();
+                    let name = match row.counterpart_name.is_empty() {
+                        true => row.counterpart_id,
+                        false => row.counterpart_name,
+                    };
+                    view! {
+                        <tr
+                            class="value-breakdown-row"
+                            title="Show the detail of this element"
+                            on:click=move |_| set_selected_object_id.set(counterpart_id.clone())
+                        >
+                            <td title=row.relationship_name>{row.relationship_type}</td>
+                            <td>{name}</td>
+                            <td>{row.counterpart_type}</td>
+                        </tr>
+                    }
+                })
+                .collect_view()}
+        </table>
+    }
+}
+
+#[server(GetElementDetail, "/api")]
+pub async fn get_element_detail(
+    model_id: String,
+    version: String,
+    id: String,
+) -> Result<ElementDetail, ModelError> {
+    use crate::model::element_graph::Path;
+    use crate::model::{app_state, parser};
+    use std::time::Instant;
+
+    if model_id.is_empty() || id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    println!(
+        "[ElementDetailViewer] get_element_detail for model: {}, cubsobject id: {}",
+        model_id, id
+    );
+    let start_time = Instant::now();
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
+
+    let element = model
+        .get_element_with_id(&id)
+        .ok_or(ModelError::ElementNotFound(id.clone()))?;
+    // Properties and facets are shown without relationships when the graph can't be built
+    let graph = match parser::read_model_graph(&app_state, &model_id, &model).await {
+        Ok(graph) => Some(graph),
+        Err(e @ (ModelError::ModelNotFound(_) | ModelError::ElementNotFound(_))) => return Err(e),
+        Err(e) => {
+            println!(
+                "[ElementDetailViewer] no relationships for {}: {}",
+                model_id, e
+            );
+            None
+        }
+    };
+
+    // Relationship id and counterpart id of each connection
+    fn to_pairs(paths: Vec<&Path>) -> Vec<(&str, &str)> {
+        paths
+            .into_iter()
+            .map(|p| (p.0.as_str(), p.1.as_str()))
+            .collect()
+    }
+    let detail = match graph.as_ref().and_then(|g| g.get_connection(&id)) {
+        Some(connector) => ElementDetail::from(
+            element,
+            &model,
+            &to_pairs(connector.get_in_id()),
+            &to_pairs(connector.get_out_id()),
+        ),
+        None => ElementDetail::from(element, &model, &[], &[]),
+    };
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!("[Execution time] get_element_detail - {:?}", elapsed_time);
+
+    Ok(detail)
+}
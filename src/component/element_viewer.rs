@@ -1,6 +1,14 @@
 use leptos::prelude::*;
+use serde_json::Value;
 
-use crate::app::RHSMode;
+use crate::app::{
+    RHSMode, DEFAULT_QUERY_DEPTH, DEFAULT_QUERY_LIMIT, TABLE_MODE, VALUE_BREAKDOWN_MODE,
+};
+use crate::component::saved_query_viewer::SavedQueryPicker;
+use crate::model::element_table::TableColumn;
+use crate::model::facet_schema::{self, FacetGroupSchema};
+use crate::model::model_error::ModelError;
+use crate::model::saved_query::SavedQuery;
 
 #[component]
 pub fn ElementViewerInput(
@@ -8,18 +16,112 @@ pub fn ElementViewerInput(
     version: ReadSignal<String>,
     types: ReadSignal<Vec<String>>,
     natures: ReadSignal<Vec<String>>,
-    set_query: WriteSignal<String>,
+    query_value: ReadSignal<String>,
+    set_query_value: WriteSignal<String>,
+    selected_facet_type: ReadSignal<String>,
+    set_selected_facet_type: WriteSignal<String>,
+    set_query: WriteSignal<Option<Value>>,
     set_rhs_mode: WriteSignal<RHSMode>,
+    on_run_saved_query: Callback<SavedQuery>,
 ) -> impl IntoView {
-    let (query_value, set_query_value) = signal("".to_string());
+    let (selected_type, set_selected_type) = signal("All".to_string());
+    let (selected_nature, set_selected_nature) = signal("All".to_string());
+    let (depth, set_depth) = signal(DEFAULT_QUERY_DEPTH.to_string());
+    let (limit, set_limit) = signal(DEFAULT_QUERY_LIMIT.to_string());
+
+    // Saved queries keep the element and facet filters of the form
+    let current_query = Signal::derive(move || SavedQuery {
+        types: selected_type.get(),
+        natures: selected_nature.get(),
+        facet_type: selected_facet_type.get(),
+        query: query_value.get(),
+        depth: depth.get().parse().unwrap_or(DEFAULT_QUERY_DEPTH as i32),
+        limit: limit.get().parse().unwrap_or(DEFAULT_QUERY_LIMIT as i32),
+        ..Default::default()
+    });
+    let load_saved_query = Callback::new(move |query: SavedQuery| {
+        set_selected_type.set(query.types);
+        set_selected_nature.set(query.natures);
+        set_selected_facet_type.set(query.facet_type);
+        set_query_value.set(query.query);
+        set_depth.set(query.depth.to_string());
+        set_limit.set(query.limit.to_string());
+    });
+
+    // Paths present in the facet group of the selected elements
+    let async_suggestions = Resource::new(
+        move || {
+            (
+                model_id.get(),
+                version.get(),
+                selected_type.get(),
+                selected_nature.get(),
+                selected_facet_type.get(),
+            )
+        },
+        |(model_id, version, types, natures, facet_type)| async move {
+            if facet_type.is_empty() {
+                return None;
+            }
+            get_pointer_suggestions(model_id, version, types, natures, facet_type)
+                .await
+                .ok()
+        },
+    );
+    let suggestions = move || async_suggestions.get().flatten();
+
+    // Warn when the pointer is not found in any element carrying the facet group
+    let pointer_warning = move || {
+        let pointer = query_value.get();
+        let group = suggestions()?;
+        if group.element_count == 0 {
+            Some(format!("No selected element has {}", group.facet_type))
+        } else if !pointer.is_empty() && !group.matches_pointer(&pointer) {
+            Some(format!(
+                "Pointer matches none of the {} elements with {}",
+                group.element_count, group.facet_type
+            ))
+        } else {
+            None
+        }
+    };
+    // Table columns are picked from the facet type and pointer inputs
+    let (mode, set_mode) = signal("".to_string());
+    let (columns, set_columns) = signal(Vec::<TableColumn>::new());
+    let add_column = move |_| {
+        let column = TableColumn {
+            facet_type: selected_facet_type.get_untracked(),
+            pointer: query_value.get_untracked(),
+        };
+        if !column.facet_type.is_empty() && !column.pointer.is_empty() {
+            set_columns.update(|c| {
+                if !c.contains(&column) {
+                    c.push(column)
+                }
+            });
+        }
+    };
+    // Sent as a json array so that pointers may contain any character
+    let columns_value = move || match columns.with(|c| c.is_empty()) {
+        true => String::new(),
+        false => serde_json::to_string(&columns.get()).unwrap_or_default(),
+    };
+
     // let clear_query = move |_| set_query_value.set(String::new());
     let clear_query_result = move |_| {
-        set_query.set("Querying...".to_string());
+        set_query.set(None);
         // set_rhs_mode.set(RHSMode::ModelStats);  //Switch RHS back to model stats
     };
 
     view! {
         <div class="flex-container-view-input">
+            <h4 class="flex-container-view-input-heading">Saved Queries</h4>
+            <SavedQueryPicker
+                current=current_query
+                on_load=load_saved_query
+                on_run=on_run_saved_query
+            />
+
             <h4 class="flex-container-view-input-heading">Element Filtering</h4>
             <div class="flex-container-view-input-row">
                 <input type="hidden" name="model_id" prop:value=model_id size=40 />
@@ -30,28 +132,54 @@ pub fn ElementViewerInput(
                 <input type="text" name="id" size=40 value="" />
 
                 <label for="types">Select Type :</label>
-                <select id="types" name="types">
+                <select
+                    id="types"
+                    name="types"
+                    prop:value=selected_type
+                    on:change=move |ev| set_selected_type.set(event_target_value(&ev))
+                >
                     {move || {
                         types
                             .get()
                             .into_iter()
                             .map(|n| {
                                 let value = n.clone();
-                                view! { <option value=n>{value}</option> }
+                                let is_selected = {
+                                    let n = n.clone();
+                                    move || selected_type.get() == n
+                                };
+                                view! {
+                                    <option value=n selected=is_selected>
+                                        {value}
+                                    </option>
+                                }
                             })
                             .collect_view()
                     }}
                 </select>
 
                 <label for="natures">Nature :</label>
-                <select id="natures" name="natures">
+                <select
+                    id="natures"
+                    name="natures"
+                    prop:value=selected_nature
+                    on:change=move |ev| set_selected_nature.set(event_target_value(&ev))
+                >
                     {move || {
                         natures
                             .get()
                             .into_iter()
                             .map(|n| {
                                 let value = n.clone();
-                                view! { <option value=n>{value}</option> }
+                                let is_selected = {
+                                    let n = n.clone();
+                                    move || selected_nature.get() == n
+                                };
+                                view! {
+                                    <option value=n selected=is_selected>
+                                        {value}
+                                    </option>
+                                }
                             })
                             .collect_view()
                     }}
@@ -61,7 +189,12 @@ pub fn ElementViewerInput(
             <h4 class="flex-container-view-input-heading">Facet Filtering</h4>
             <div class="flex-container-view-input-row">
                 <label for="natures">Facet Type :</label>
-                <select id="facet_type" name="facet_type">
+                <select
+                    id="facet_type"
+                    name="facet_type"
+                    prop:value=selected_facet_type
+                    on:change=move |ev| set_selected_facet_type.set(event_target_value(&ev))
+                >
                     <option value="">None</option>
                     <option value="dynamicFacets">Dynamic Facets</option>
                     <option value="coreFacets">Core Facets</option>
@@ -72,21 +205,118 @@ pub fn ElementViewerInput(
                     type="text"
                     name="query"
                     size=50
+                    list="pointer-suggestions"
+                    autocomplete="off"
                     prop:value=query_value
                     on:input=move |ev| {
                         set_query_value.set(event_target_value(&ev));
                     }
                 />
+                <datalist id="pointer-suggestions">
+                    <Transition>
+                        {move || {
+                            suggestions()
+                                .map(|group| {
+                                    group
+                                        .paths
+                                        .into_iter()
+                                        .map(|path| {
+                                            let label = format!(
+                                                "{} ({:.0}%)",
+                                                path.value_types.join(", "),
+                                                path.occurrence_ratio * 100.0,
+                                            );
+                                            view! {
+                                                <option value=facet_schema::to_query_pointer(
+                                                    &path.pointer,
+                                                )>{label}</option>
+                                            }
+                                        })
+                                        .collect_view()
+                                })
+                        }}
+                    </Transition>
+                </datalist>
+                <Transition>
+                    {move || {
+                        pointer_warning()
+                            .map(|warning| {
+                                view! { <span class="pointer-warning">{warning}</span> }
+                            })
+                    }}
+                </Transition>
 
                 <label for="query">Detail:</label>
                 <input type="checkbox" name="is_detail" value="is_detail" />
 
+                <label for="mode">Mode:</label>
+                <select
+                    id="mode"
+                    name="mode"
+                    on:change=move |ev| set_mode.set(event_target_value(&ev))
+                >
+                    <option value="">Elements</option>
+                    <option value=VALUE_BREAKDOWN_MODE>Value breakdown</option>
+                    <option value=TABLE_MODE>Table</option>
+                </select>
+
             </div>
 
+            <input type="hidden" name="columns" prop:value=columns_value />
+            {move || {
+                (mode.get() == TABLE_MODE)
+                    .then(|| {
+                        view! {
+                            <h4 class="flex-container-view-input-heading">Table Columns</h4>
+                            <div class="flex-container-view-input-row">
+                                <span>"id, name, type"</span>
+                                {move || {
+                                    columns
+                                        .get()
+                                        .into_iter()
+                                        .map(|column| {
+                                            let label = column.form_value();
+                                            view! {
+                                                <span class="table-column-chip">
+                                                    {label}
+                                                    <button
+                                                        type="button"
+                                                        on:click=move |_| {
+                                                            set_columns.update(|c| c.retain(|x| *x != column))
+                                                        }
+                                                    >
+                                                        "×"
+                                                    </button>
+                                                </span>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                                <button
+                                    type="button"
+                                    title="Add the facet type and pointer as a column"
+                                    on:click=add_column
+                                >
+                                    "Add column"
+                                </button>
+                            </div>
+                        }
+                    })
+            }}
+
             <h4 class="flex-container-view-input-heading">Output Filtering</h4>
             <div class="flex-container-view-input-row">
                 <label for="depth">Depth:</label>
-                <input type="number" id="depth" name="depth" min="0" max="100" step="1" value="3" />
+                <input
+                    type="number"
+                    id="depth"
+                    name="depth"
+                    min="0"
+                    max="100"
+                    step="1"
+                    prop:value=depth
+                    on:input=move |ev| set_depth.set(event_target_value(&ev))
+                />
                 <label for="limit">Limit:</label>
                 <input
                     type="number"
@@ -95,7 +325,8 @@ pub fn ElementViewerInput(
                     min="0"
                     max="5000"
                     step="1"
-                    value="30"
+                    prop:value=limit
+                    on:input=move |ev| set_limit.set(event_target_value(&ev))
                 />
                 <button type="submit" on:click=clear_query_result>
                     Run Query
@@ -105,3 +336,27 @@ pub fn ElementViewerInput(
         </div>
     }
 }
+
+#[server(GetPointerSuggestions, "/api")]
+pub async fn get_pointer_suggestions(
+    model_id: String,
+    version: String,
+    types: String,
+    natures: String,
+    facet_type: String,
+) -> Result<FacetGroupSchema, ModelError> {
+    use crate::model::cubs_model::FacetType;
+    use crate::model::{app_state, parser};
+
+    let facet_type = FacetType::from_form_value(&facet_type).ok_or(ModelError::InvalidInput)?;
+    if model_id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
+
+    let elements = model.get_filtered_elements("", &types, &natures);
+
+    Ok(FacetGroupSchema::from_elements(&elements, &facet_type))
+}
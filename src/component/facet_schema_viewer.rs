@@ -0,0 +1,140 @@
+use leptos::prelude::*;
+
+use crate::model::facet_schema::{FacetGroupSchema, ModelSchema, PathSchema, TypeSchema};
+use crate::model::model_error::ModelError;
+
+#[component]
+pub fn FacetSchemaViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+) -> impl IntoView {
+    // Infer the schema again when the model changes
+    let async_schema = Resource::new(
+        move || (model_id.get(), selected_version.get()),
+        |(model_id, version)| async move { get_facet_schema(model_id, version).await },
+    );
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Facet Schema</h2>
+            <Transition fallback=move || view! { <p>"Inferring schema..."</p> }>
+                {move || match async_schema.get() {
+                    Some(Ok(schema)) => view! { <ModelSchemaRender schema=schema /> }.into_any(),
+                    Some(Err(e)) => {
+                        view! { <span class="json-error">{format!("Error: {}", e)}</span> }
+                            .into_any()
+                    }
+                    None => view! { <span>"Loading ... "</span> }.into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn ModelSchemaRender(schema: ModelSchema) -> impl IntoView {
+    view! {
+        <div class="facet-schema-tree">
+            {schema
+                .types
+                .into_iter()
+                .map(|type_schema| view! { <TypeSchemaRender type_schema=type_schema /> })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn TypeSchemaRender(type_schema: TypeSchema) -> impl IntoView {
+    view! {
+        <details class="facet-schema-type">
+            <summary>{type_schema.type_} " (" {type_schema.element_count} ")"</summary>
+            {type_schema
+                .groups
+                .into_iter()
+                .map(|group| view! { <FacetGroupSchemaRender group=group /> })
+                .collect_view()}
+        </details>
+    }
+}
+
+#[component]
+fn FacetGroupSchemaRender(group: FacetGroupSchema) -> impl IntoView {
+    view! {
+        <details class="facet-schema-group">
+            <summary>
+                {group.facet_type} " (" {group.element_count} " elements, " {group.paths.len()}
+                " paths)"
+            </summary>
+            <table class="facet-schema-table">
+                <tr>
+                    <th>"Path"</th>
+                    <th>"Types"</th>
+                    <th>"Occurrence"</th>
+                    <th>"Examples"</th>
+                </tr>
+                {group
+                    .paths
+                    .into_iter()
+                    .map(|path| view! { <PathSchemaRender path=path /> })
+                    .collect_view()}
+            </table>
+        </details>
+    }
+}
+
+#[component]
+fn PathSchemaRender(path: PathSchema) -> impl IntoView {
+    // Indent each path below its parent and only show the last segment
+    let depth = path.pointer.matches('/').count().saturating_sub(1);
+    let segment = path
+        .pointer
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    view! {
+        <tr>
+            <td title=path.pointer.clone() style=format!("padding-left: {}em", depth)>
+                {segment}
+            </td>
+            <td>{path.value_types.join(", ")}</td>
+            <td>
+                {format!("{:.1}%", path.occurrence_ratio * 100.0)} " (" {path.occurrence_count}
+                ")"
+            </td>
+            <td class="facet-schema-examples">{path.examples.join(", ")}</td>
+        </tr>
+    }
+}
+
+#[server(GetFacetSchema, "/api")]
+pub async fn get_facet_schema(
+    model_id: String,
+    version: String,
+) -> Result<ModelSchema, ModelError> {
+    use crate::model::{app_state, parser};
+    use std::time::Instant;
+
+    if model_id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    println!(
+        "[FacetSchemaViewer] get_facet_schema for model: {}",
+        model_id
+    );
+    let start_time = Instant::now();
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
+
+    let schema = ModelSchema::from(&model);
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!("[Execution time] get_facet_schema - {:?}", elapsed_time);
+
+    Ok(schema)
+}
@@ -0,0 +1,368 @@
+use leptos::prelude::*;
+
+use crate::app::RHSMode;
+use crate::model::graph_layout::{get_type_color, GraphLayout, LayoutNode};
+use crate::model::model_error::ModelError;
+
+// Levels of parents and children around the target
+const DEFAULT_ANCESTOR_LEVEL: u32 = 1;
+const DEFAULT_CHILDREN_LEVEL: u32 = 2;
+const MAX_GRAPH_LEVEL: u32 = 5;
+
+const NODE_RADIUS: f64 = 16.0;
+const MIN_VIEW_WIDTH: f64 = 640.0;
+const MIN_VIEW_HEIGHT: f64 = 480.0;
+const ZOOM_STEP: f64 = 1.15;
+
+#[component]
+pub fn GraphViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    selected_object_id: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let (ancestor_level, set_ancestor_level) = signal(DEFAULT_ANCESTOR_LEVEL);
+    let (children_level, set_children_level) = signal(DEFAULT_CHILDREN_LEVEL);
+
+    let async_layout = Resource::new(
+        move || {
+            (
+                model_id.get(),
+                selected_version.get(),
+                selected_object_id.get(),
+                ancestor_level.get(),
+                children_level.get(),
+            )
+        },
+        |(model_id, version, id, ancestor_level, children_level)| async move {
+            get_graph_layout(model_id, version, id, ancestor_level, children_level).await
+        },
+    );
+
+    let level_options = move |selected: ReadSignal<u32>| {
+        (0..=MAX_GRAPH_LEVEL)
+            .map(|level| {
+                view! {
+                    <option value=level selected=move || selected.get() == level>
+                        {level}
+                    </option>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Relationship Graph</h2>
+            <div class="graph-viewer-input">
+                <label>
+                    "Parents "
+                    <select on:change=move |ev| {
+                        set_ancestor_level.set(event_target_value(&ev).parse().unwrap_or(DEFAULT_ANCESTOR_LEVEL))
+                    }>{level_options(ancestor_level)}</select>
+                </label>
+                <label>
+                    "Children "
+                    <select on:change=move |ev| {
+                        set_children_level.set(event_target_value(&ev).parse().unwrap_or(DEFAULT_CHILDREN_LEVEL))
+                    }>{level_options(children_level)}</select>
+                </label>
+                <button
+                    type="button"
+                    on:click=move |_| set_rhs_mode.set(RHSMode::ElementDetail)
+                >
+                    "Detail"
+                </button>
+            </div>
+            <Transition fallback=move || view! { <p>"Loading graph..."</p> }>
+                {move || match async_layout.get() {
+                    Some(Ok(layout)) => {
+                        view! {
+                            <GraphRender
+                                layout=layout
+                                set_selected_object_id=set_selected_object_id
+                            />
+                        }
+                            .into_any()
+                    }
+                    Some(Err(e)) => {
+                        view! { <span class="json-error">{format!("Error: {}", e)}</span> }
+                            .into_any()
+                    }
+                    None => view! { <span>"Loading ... "</span> }.into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn GraphRender(layout: GraphLayout, set_selected_object_id: WriteSignal<String>) -> impl IntoView {
+    // View box centered on the target, zoom and pan only move the view box
+    let width = layout.width.max(MIN_VIEW_WIDTH);
+    let height = layout.height.max(MIN_VIEW_HEIGHT);
+    let target_x = layout
+        .nodes
+        .iter()
+        .find(|n| n.id == layout.target_id)
+        .map_or(layout.width / 2.0, |n| n.x);
+    let initial_view = (target_x - width / 2.0, 0.0, width, height);
+    let (view_box, set_view_box) = signal(initial_view);
+
+    // Last pointer position while dragging and whether the pointer moved since pressed
+    let drag = StoredValue::new(None::<(i32, i32)>);
+    let is_dragged = StoredValue::new(false);
+    let svg_ref = NodeRef::<leptos::svg::Svg>::new();
+    let (hovered, set_hovered) = signal(None::<LayoutNode>);
+
+    // Size of one screen pixel in view box units
+    let get_pixel_size = move || {
+        let (_, _, w, h) = view_box.get_untracked();
+        svg_ref.get_untracked().map_or(1.0, |svg| {
+            let (client_w, client_h) = (
+                svg.client_width().max(1) as f64,
+                svg.client_height().max(1) as f64,
+            );
+            (w / client_w).max(h / client_h)
+        })
+    };
+
+    let on_wheel = move |ev: leptos::ev::WheelEvent| {
+        ev.prevent_default();
+        let factor = if ev.delta_y() > 0.0 {
+            ZOOM_STEP
+        } else {
+            1.0 / ZOOM_STEP
+        };
+        let pixel_size = get_pixel_size();
+        set_view_box.update(|(x, y, w, h)| {
+            // Keep the point under the pointer in place
+            let (px, py) = (
+                *x + ev.offset_x() as f64 * pixel_size,
+                *y + ev.offset_y() as f64 * pixel_size,
+            );
+            *x = px - (px - *x) * factor;
+            *y = py - (py - *y) * factor;
+            *w *= factor;
+            *h *= factor;
+        });
+    };
+
+    let on_mouse_move = move |ev: leptos::ev::MouseEvent| {
+        let Some((last_x, last_y)) = drag.get_value() else {
+            return;
+        };
+        let (dx, dy) = (ev.client_x() - last_x, ev.client_y() - last_y);
+        if dx.abs() + dy.abs() > 2 {
+            is_dragged.set_value(true);
+        }
+        let pixel_size = get_pixel_size();
+        set_view_box.update(|(x, y, _, _)| {
+            *x -= dx as f64 * pixel_size;
+            *y -= dy as f64 * pixel_size;
+        });
+        drag.set_value(Some((ev.client_x(), ev.client_y())));
+    };
+
+    let types = layout.get_types();
+    let edges = layout
+        .edges
+        .iter()
+        .map(|edge| {
+            let (source, target) = (&layout.nodes[edge.source], &layout.nodes[edge.target]);
+            // Stop the line at the node border so the arrow head stays visible
+            let (dx, dy) = (target.x - source.x, target.y - source.y);
+            let length = (dx * dx + dy * dy).sqrt().max(1.0);
+            let (x2, y2) = (
+                target.x - dx / length * NODE_RADIUS,
+                target.y - dy / length * NODE_RADIUS,
+            );
+            view! {
+                <line
+                    class="graph-viewer-edge"
+                    x1=source.x
+                    y1=source.y
+                    x2=x2
+                    y2=y2
+                    marker-end="url(#graph-viewer-arrow)"
+                />
+            }
+        })
+        .collect_view();
+
+    let target_id = layout.target_id.clone();
+    let nodes = layout
+        .nodes
+        .into_iter()
+        .map(|node| {
+            let id = node.id.clone();
+            let is_target = node.id == target_id;
+            let label = match node.name.chars().count() > 18 {
+                true => format!("{}…", node.name.chars().take(17).collect::<String>()),
+                false => node.name.clone(),
+            };
+            let (x, y) = (node.x, node.y);
+            let fill = get_type_color(&node.type_);
+            view! {
+                <g
+                    class="graph-viewer-node"
+                    class:graph-viewer-target=is_target
+                    on:mouseenter=move |_| set_hovered.set(Some(node.clone()))
+                    on:click=move |_| {
+                        if !is_dragged.get_value() {
+                            set_selected_object_id.set(id.clone());
+                        }
+                    }
+                >
+                    <circle cx=x cy=y r=NODE_RADIUS fill=fill />
+                    <text x=x y=y + NODE_RADIUS + 14.0 text-anchor="middle">
+                        {label}
+                    </text>
+                </g>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="graph-viewer-toolbar">
+            <button type="button" on:click=move |_| set_view_box.set(initial_view)>
+                "Reset view"
+            </button>
+            <span>
+                {move || {
+                    hovered
+                        .get()
+                        .map(|n| format!("{} : {} ({})", n.name, n.type_, n.id))
+                        .unwrap_or_else(|| "Click a node to center on it".to_string())
+                }}
+            </span>
+        </div>
+        <svg
+            node_ref=svg_ref
+            class="graph-viewer"
+            viewBox=move || {
+                let (x, y, w, h) = view_box.get();
+                format!("{} {} {} {}", x, y, w, h)
+            }
+            on:wheel=on_wheel
+            on:mousedown=move |ev| {
+                is_dragged.set_value(false);
+                drag.set_value(Some((ev.client_x(), ev.client_y())));
+            }
+            on:mousemove=on_mouse_move
+            on:mouseup=move |_| drag.set_value(None)
+            on:mouseleave=move |_| drag.set_value(None)
+        >
+            <defs>
+                <marker
+                    id="graph-viewer-arrow"
+                    viewBox="0 0 10 10"
+                    refX="10"
+                    refY="5"
+                    markerWidth="7"
+                    markerHeight="7"
+                    orient="auto"
+                >
+                    <path d="M 0 0 L 10 5 L 0 10 z" />
+                </marker>
+            </defs>
+            {edges}
+            {nodes}
+        </svg>
+        <div class="graph-viewer-legend">
+            {types
+                .into_iter()
+                .map(|type_| {
+                    view! {
+                        <span class="table-column-chip">
+                            <span
+                                class="graph-viewer-swatch"
+                                style:background-color=get_type_color(&type_)
+                            ></span>
+                            {type_}
+                        </span>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[server(GetGraphLayout, "/api")]
+pub async fn get_graph_layout(
+    model_id: String,
+    version: String,
+    id: String,
+    ancestor_level: u32,
+    children_level: u32,
+) -> Result<GraphLayout, ModelError> {
+    use crate::model::{app_state, element_graph_parser::ElementGraphParser, parser};
+    use std::time::Instant;
+
+    // Larger subgraphs are refused, the SVG stops being readable
+    const MAX_GRAPH_NODES: usize = 500;
+
+    if model_id.is_empty() || id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    println!(
+        "[GraphViewer] get_graph_layout for model: {}, cubsobject id: {}",
+        model_id, id
+    );
+    let start_time = Instant::now();
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
+
+    let graph = parser::read_model_graph(&app_state, &model_id, &model).await?;
+    let parse_graph = ElementGraphParser::parse_graph(
+        &graph,
+        &id,
+        ancestor_level.min(MAX_GRAPH_LEVEL),
+        children_level.min(MAX_GRAPH_LEVEL),
+    )?;
+
+    let connectors = parse_graph.get_connectors();
+    if connectors.len() > MAX_GRAPH_NODES {
+        return Err(ModelError::ParsingError(format!(
+            "graph of {} elements, reduce the levels below {}",
+            connectors.len(),
+            MAX_GRAPH_NODES
+        )));
+    }
+
+    // Nodes with name and type, edges from the out connections kept in the subgraph
+    let nodes = connectors
+        .iter()
+        .map(|connector| {
+            let id = connector.get_element_id();
+            let element = model.get_element_with_id(id);
+            (
+                id.to_string(),
+                element.map(|e| e.name.clone()).unwrap_or_default(),
+                element.map(|e| e.type_.clone()).unwrap_or_default(),
+            )
+        })
+        .collect();
+    let edges: Vec<(String, String, String)> = connectors
+        .iter()
+        .flat_map(|connector| {
+            connector.get_out_id().into_iter().map(|path| {
+                (
+                    path.0.clone(),
+                    connector.get_element_id().to_string(),
+                    path.1.clone(),
+                )
+            })
+        })
+        .collect();
+    let layout = GraphLayout::from(&id, nodes, &edges);
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!("[Execution time] get_graph_layout - {:?}", elapsed_time);
+
+    Ok(layout)
+}
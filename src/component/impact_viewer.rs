@@ -0,0 +1,337 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_use::{use_clipboard, UseClipboardReturn};
+
+use crate::app::RHSMode;
+use crate::model::cubs_model::RelationshipDirection;
+use crate::model::impact::ImpactAnalysis;
+use crate::model::model_dict::ElementCounts;
+use crate::model::model_error::ModelError;
+
+const IMPACT_PAGE_SIZE: usize = 50;
+
+#[component]
+pub fn ImpactViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    selected_object_id: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let UseClipboardReturn { copy, copied, .. } = use_clipboard();
+    let (direction, set_direction) = signal(RelationshipDirection::Child);
+    let (types_input, set_types_input) = signal(String::new());
+    // Relationship types applied, empty follows every relationship
+    let (relationship_types, set_relationship_types) = signal(Vec::<String>::new());
+    let (page, set_page) = signal(0usize);
+    let (error, set_error) = signal(None::<String>);
+
+    // Back to the first page for a new analysis
+    Effect::new(move |_| {
+        model_id.track();
+        selected_version.track();
+        selected_object_id.track();
+        direction.track();
+        relationship_types.track();
+        set_page.set(0);
+    });
+
+    let async_analysis = Resource::new(
+        move || {
+            (
+                model_id.get(),
+                selected_version.get(),
+                selected_object_id.get(),
+                direction.get(),
+                relationship_types.get(),
+                page.get(),
+            )
+        },
+        |(model_id, version, id, direction, types, page)| async move {
+            get_impact_analysis(
+                model_id,
+                version,
+                id,
+                direction,
+                types,
+                page,
+                IMPACT_PAGE_SIZE,
+            )
+            .await
+        },
+    );
+
+    let apply_types = move || {
+        let types = types_input
+            .get_untracked()
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        set_relationship_types.set(types);
+    };
+
+    // The export holds the whole closure, not only the page shown
+    let copy_csv = move |_| {
+        let copy = copy.clone();
+        let (model, version, id) = (
+            model_id.get_untracked(),
+            selected_version.get_untracked(),
+            selected_object_id.get_untracked(),
+        );
+        let (direction, types) = (
+            direction.get_untracked(),
+            relationship_types.get_untracked(),
+        );
+        spawn_local(async move {
+            match export_impact_analysis(model, version, id, direction, types).await {
+                Ok(csv) => copy(&csv),
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Impact Analysis</h2>
+            <div class="compare-viewer-input">
+                <select on:change=move |ev| {
+                    set_direction
+                        .set(
+                            match event_target_value(&ev).as_str() {
+                                "upstream" => RelationshipDirection::Parent,
+                                _ => RelationshipDirection::Child,
+                            },
+                        )
+                }>
+                    <option value="downstream" selected=move || direction.get() == RelationshipDirection::Child>
+                        "Downstream"
+                    </option>
+                    <option value="upstream" selected=move || direction.get() == RelationshipDirection::Parent>
+                        "Upstream"
+                    </option>
+                </select>
+                <input
+                    type="text"
+                    placeholder="Relationship types, comma separated, empty for all"
+                    prop:value=types_input
+                    on:input=move |ev| set_types_input.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            ev.prevent_default();
+                            apply_types();
+                        }
+                    }
+                />
+                <button type="button" on:click=move |_| apply_types()>
+                    "Apply"
+                </button>
+                <button type="button" on:click=copy_csv>
+                    {move || if copied.get() { "Copied" } else { "Copy CSV" }}
+                </button>
+            </div>
+            {move || error.get().map(|e| view! { <span class="json-error">{format!("Error: {}", e)}</span> })}
+            <Transition fallback=move || view! { <p>"Computing impact..."</p> }>
+                {move || match async_analysis.get() {
+                    Some(Ok(analysis)) => {
+                        view! {
+                            <ImpactRender
+                                analysis=analysis
+                                set_page=set_page
+                                set_selected_object_id=set_selected_object_id
+                                set_rhs_mode=set_rhs_mode
+                            />
+                        }
+                            .into_any()
+                    }
+                    Some(Err(e)) => {
+                        view! { <span class="json-error">{format!("Error: {}", e)}</span> }
+                            .into_any()
+                    }
+                    None => view! { <span>"Loading ... "</span> }.into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn ImpactRender(
+    analysis: ImpactAnalysis,
+    set_page: WriteSignal<usize>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let page = analysis.page;
+    let page_count = analysis.get_page_count();
+
+    view! {
+        <h4 class="label-model-stats">{analysis.total_count} " impacted elements"</h4>
+        <div class="impact-viewer-summary">
+            <ImpactCountTable label="Type" counts=analysis.by_type />
+            <ImpactCountTable label="Nature" counts=analysis.by_nature />
+        </div>
+        <div class="aggregation-table">
+            <table>
+                <tr>
+                    <th>"Distance"</th>
+                    <th>"Name"</th>
+                    <th>"Type"</th>
+                    <th>"Nature"</th>
+                </tr>
+                {analysis
+                    .rows
+                    .into_iter()
+                    .map(|row| {
+                        let id = row.id.clone();
+                        let name = match row.name.is_empty() {
+                            true => row.id,
+                            false => row.name,
+                        };
+                        view! {
+                            <tr
+                                class="value-breakdown-row"
+                                title="Show the detail of this element"
+                                on:click=move |_| {
+                                    set_selected_object_id.set(id.clone());
+                                    set_rhs_mode.set(RHSMode::ElementDetail);
+                                }
+                            >
+                                <td>{row.distance}</td>
+                                <td>{name}</td>
+                                <td>{row.type_}</td>
+                                <td>{row.nature}</td>
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+            </table>
+        </div>
+        <div class="impact-viewer-pager">
+            <button type="button" disabled=page == 0 on:click=move |_| set_page.set(page.saturating_sub(1))>
+                "Previous"
+            </button>
+            <span>"Page " {page + 1} " of " {page_count.max(1)}</span>
+            <button type="button" disabled=page + 1 >= page_count on:click=move |_| set_page.set(page + 1)>
+                "Next"
+            </button>
+        </div>
+    }
+}
+
+#[component]
+fn ImpactCountTable(label: &'static str, counts: ElementCounts) -> impl IntoView {
+    view! {
+        <table>
+            <tr>
+                <th>{label}</th>
+                <th>"Count"</th>
+            </tr>
+            {counts
+                .value
+                .into_iter()
+                .map(|count| {
+                    view! {
+                        <tr>
+                            <td>{count.element}</td>
+                            <td>{count.count}</td>
+                        </tr>
+                    }
+                })
+                .collect_view()}
+        </table>
+    }
+}
+
+// Model version and the closure of the element, only following the relationship types given
+#[cfg(feature = "ssr")]
+async fn read_impact_closure(
+    model_id: &str,
+    version: &str,
+    id: &str,
+    direction: &RelationshipDirection,
+    relationship_types: &[String],
+) -> Result<
+    (
+        std::sync::Arc<crate::model::cubs_model::ModelData>,
+        Vec<(String, u32)>,
+    ),
+    ModelError,
+> {
+    use crate::model::{app_state, parser};
+
+    if model_id.is_empty() || id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, model_id, version).await?;
+    let graph = parser::read_model_graph(&app_state, model_id, &model).await?;
+
+    if graph.get_connection(id).is_none() {
+        return Err(ModelError::ElementNotFound(id.to_string()));
+    }
+    let closure = graph.get_closure(id, direction, |relationship_id| {
+        relationship_types.is_empty()
+            || model
+                .get_relationship_with_id(relationship_id)
+                .is_some_and(|r| relationship_types.contains(&r.type_))
+    });
+
+    Ok((model, closure))
+}
+
+#[server(GetImpactAnalysis, "/api")]
+pub async fn get_impact_analysis(
+    model_id: String,
+    version: String,
+    id: String,
+    direction: RelationshipDirection,
+    relationship_types: Vec<String>,
+    page: usize,
+    page_size: usize,
+) -> Result<ImpactAnalysis, ModelError> {
+    use std::time::Instant;
+
+    const MAX_IMPACT_PAGE_SIZE: usize = 500;
+
+    println!(
+        "[ImpactViewer] get_impact_analysis for model: {}, cubsobject id: {}, direction: {:?}",
+        model_id, id, direction
+    );
+    let start_time = Instant::now();
+
+    let (model, closure) =
+        read_impact_closure(&model_id, &version, &id, &direction, &relationship_types).await?;
+    let elements: Vec<_> = closure
+        .iter()
+        .filter_map(|(id, distance)| Some((model.get_element_with_id(id)?, *distance)))
+        .collect();
+    let analysis = ImpactAnalysis::from(&elements, page, page_size.min(MAX_IMPACT_PAGE_SIZE));
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!("[Execution time] get_impact_analysis - {:?}", elapsed_time);
+
+    Ok(analysis)
+}
+
+#[server(ExportImpactAnalysis, "/api")]
+pub async fn export_impact_analysis(
+    model_id: String,
+    version: String,
+    id: String,
+    direction: RelationshipDirection,
+    relationship_types: Vec<String>,
+) -> Result<String, ModelError> {
+    use crate::model::impact::{get_impact_rows, impact_rows_to_csv};
+
+    let (model, closure) =
+        read_impact_closure(&model_id, &version, &id, &direction, &relationship_types).await?;
+    let elements: Vec<_> = closure
+        .iter()
+        .filter_map(|(id, distance)| Some((model.get_element_with_id(id)?, *distance)))
+        .collect();
+
+    Ok(impact_rows_to_csv(&get_impact_rows(&elements)))
+}
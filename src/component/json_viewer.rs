@@ -1,21 +1,135 @@
-use leptos::{logging::log, prelude::*};
-use serde_json::{json, value, Value};
+use std::collections::HashSet;
 
-use crate::app::RHSMode;
+use leptos::{html::Div, logging::log, prelude::*};
+use leptos_use::{use_clipboard, UseClipboardReturn};
+use serde_json::Value;
+
+use crate::app::{QueryModel, RHSMode};
+use crate::model::cubs_model::FacetType;
+use crate::model::{facet_schema, json_search};
 
 const PADDING: i32 = 10;
 const COLLAPSED_OBJECT_WHEN_MORE_THAN_LEVEL: i32 = 2;
+const ARRAY_WINDOW_SIZE: usize = 100;
+
+// Actions on the facet json pointer of a key
+#[derive(Clone, Copy)]
+struct PathActions {
+    // Facet type form value and pointer of a node path, if it points into a facet group
+    resolve: Callback<Vec<String>, Option<(String, String)>>,
+    copy: Callback<String>,
+    query: Callback<(String, String)>,
+}
+
+// Search matches and expand commands shared by all nodes
+#[derive(Clone, Copy)]
+struct SearchState {
+    matches: Memo<HashSet<String>>,
+    ancestors: Memo<HashSet<String>>,
+    current_match: Memo<Option<String>>,
+    // Generation and expanded flag of the last expand all / collapse all
+    expand_command: ReadSignal<(usize, bool)>,
+}
 
 #[component]
 pub fn JsonViewer(
-    json_value: Memo<Option<Value>>,
+    json_value: ReadSignal<Option<Value>>,
     collapsed: bool,
     set_selected_object_id: WriteSignal<String>,
     set_rhs_mode: WriteSignal<RHSMode>,
+    result_query: ReadSignal<Option<QueryModel>>,
+    on_query_path: Callback<(String, String)>,
 ) -> impl IntoView {
+    let UseClipboardReturn { copy, .. } = use_clipboard();
+
+    // Pointers are relative to the query that produced the results
+    let path_actions = PathActions {
+        resolve: Callback::new(move |path: Vec<String>| {
+            let query = result_query.get_untracked()?;
+            let facet_type = FacetType::from_form_value(&query.facet_type);
+            let is_detail = query.is_detail.is_some_and(|v| v == "is_detail");
+            facet_schema::facet_pointer_from_result_path(
+                &path,
+                facet_type.as_ref(),
+                &query.query,
+                is_detail,
+            )
+            .map(|(facet_type, pointer)| (facet_type.form_value().to_string(), pointer))
+        }),
+        copy: Callback::new(move |pointer: String| copy(&pointer)),
+        query: on_query_path,
+    };
+
+    // Search
+    let (search_text, set_search_text) = signal(String::new());
+    let (current_index, set_current_index) = signal(0_usize);
+    let (expand_command, set_expand_command) = signal((0_usize, false));
+    let match_list = Memo::new(move |_| {
+        let text = search_text.get();
+        json_value.with(|v| {
+            v.as_ref()
+                .map(|v| json_search::find_matches(v, &text))
+                .unwrap_or_default()
+        })
+    });
+    let search = SearchState {
+        matches: Memo::new(move |_| match_list.with(|m| m.iter().cloned().collect())),
+        ancestors: Memo::new(move |_| match_list.with(|m| json_search::get_ancestors(m))),
+        current_match: Memo::new(move |_| match_list.with(|m| m.get(current_index.get()).cloned())),
+        expand_command,
+    };
+    let match_count = move || match_list.with(|m| m.len());
+    let next_match = move |step: usize| {
+        let count = match_count();
+        if count > 0 {
+            set_current_index.update(|i| *i = (*i + step) % count);
+        }
+    };
+    let expand_all = move |is_expanded: bool| {
+        set_expand_command.update(|(generation, expanded)| {
+            *generation += 1;
+            *expanded = is_expanded;
+        })
+    };
+
     //Consume and render
     view! {
         <div class="json-container">
+            <div class="json-viewer-toolbar">
+                <input
+                    type="text"
+                    placeholder="Search keys and values"
+                    prop:value=search_text
+                    on:input=move |ev| {
+                        set_current_index.set(0);
+                        set_search_text.set(event_target_value(&ev));
+                    }
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            ev.prevent_default();
+                            next_match(1);
+                        }
+                    }
+                />
+                <span>
+                    {move || match match_count() {
+                        0 => "No match".to_string(),
+                        count => format!("{} / {}", current_index.get() + 1, count),
+                    }}
+                </span>
+                <button type="button" on:click=move |_| next_match(match_count().max(1) - 1)>
+                    "Previous"
+                </button>
+                <button type="button" on:click=move |_| next_match(1)>
+                    "Next"
+                </button>
+                <button type="button" on:click=move |_| expand_all(true)>
+                    "Expand all"
+                </button>
+                <button type="button" on:click=move |_| expand_all(false)>
+                    "Collapse all"
+                </button>
+            </div>
             <div class="json-viewer">
                 {move || {
                     log!("Rendering Json viewer");
@@ -27,6 +141,9 @@ pub fn JsonViewer(
                                 is_last=true
                                 collapsed=collapsed
                                 key=None
+                                path=Vec::new()
+                                path_actions=path_actions
+                                search=search
                                 set_selected_object_id=set_selected_object_id
                                 set_rhs_mode=set_rhs_mode
                             />
@@ -49,6 +166,9 @@ fn JsonNode(
     is_last: bool,
     collapsed: bool,
     key: Option<String>,
+    path: Vec<String>,
+    path_actions: PathActions,
+    search: SearchState,
     set_selected_object_id: WriteSignal<String>,
     set_rhs_mode: WriteSignal<RHSMode>,
 ) -> impl IntoView {
@@ -58,8 +178,12 @@ fn JsonNode(
         Value::Object(obj) => {
             let (is_collapsed, set_collapsed) = signal(collapsed);
             set_collapsed.update(|c| *c = *c || level > COLLAPSED_OBJECT_WHEN_MORE_THAN_LEVEL);
+            follow_search(search, json_search::to_pointer(&path), set_collapsed);
+            let is_built = build_once_expanded(is_collapsed);
             let entries: Vec<(String, Value)> = obj.into_iter().collect();
             let obj_len = entries.len();
+            let entries = StoredValue::new(entries);
+            let path = StoredValue::new(path);
 
             if obj_len == 0 {
                 view! {
@@ -84,34 +208,63 @@ fn JsonNode(
                         </span>
                         <span class="json-brace">"{"</span>
                         <div class="json-object-content" class:collapsed=is_collapsed>
-                            {entries
-                                .into_iter()
-                                .enumerate()
-                                .map(|(i, (key, val))| {
-                                    let is_last_item = i == obj_len - 1;
-                                    let clone_key = Some(key.clone());
-                                    view! {
-                                        <div
-                                            class="json-property"
-                                            style=format!("margin-left: {}px;", (level) * PADDING)
-                                        >
-                                            <span class="json-key">"\""</span>
-                                            <span class="json-key-text">{key}</span>
-                                            <span class="json-key">"\""</span>
-                                            <span class="json-colon">": "</span>
-                                            <JsonNode
-                                                value=val
-                                                level=level + 1
-                                                is_last=is_last_item
-                                                collapsed=collapsed
-                                                key=clone_key
-                                                set_selected_object_id=set_selected_object_id
-                                                set_rhs_mode=set_rhs_mode
-                                            />
-                                        </div>
-                                    }
-                                })
-                                .collect_view()}
+                            {move || {
+                                is_built
+                                    .get()
+                                    .then(|| {
+                                        entries
+                                            .get_value()
+                                            .into_iter()
+                                            .enumerate()
+                                            .map(|(i, (key, val))| {
+                                                let is_last_item = i == obj_len - 1;
+                                                let clone_key = Some(key.clone());
+                                                let mut child_path = path.get_value();
+                                                child_path.push(key.clone());
+                                                let facet_pointer = path_actions
+                                                    .resolve
+                                                    .run(child_path.clone());
+                                                let (node_ref, is_match, is_current) = search_match(
+                                                    search,
+                                                    json_search::to_pointer(&child_path),
+                                                );
+                                                view! {
+                                                    <div
+                                                        class="json-property"
+                                                        class:json-match=is_match
+                                                        class:json-match-current=is_current
+                                                        node_ref=node_ref
+                                                        style=format!(
+                                                            "margin-left: {}px;",
+                                                            (level) * PADDING,
+                                                        )
+                                                    >
+                                                        <span class="json-key">"\""</span>
+                                                        <JsonKey
+                                                            key=key
+                                                            facet_pointer=facet_pointer
+                                                            path_actions=path_actions
+                                                        />
+                                                        <span class="json-key">"\""</span>
+                                                        <span class="json-colon">": "</span>
+                                                        <JsonNode
+                                                            value=val
+                                                            level=level + 1
+                                                            is_last=is_last_item
+                                                            collapsed=collapsed
+                                                            key=clone_key
+                                                            path=child_path
+                                                            path_actions=path_actions
+                                                            search=search
+                                                            set_selected_object_id=set_selected_object_id
+                                                            set_rhs_mode=set_rhs_mode
+                                                        />
+                                                    </div>
+                                                }
+                                            })
+                                            .collect_view()
+                                    })
+                            }}
                             <div style=format!("margin-left: {}px;", PADDING + 5)>
                                 <span class="json-brace">"}"</span>
                                 {if !is_last {
@@ -128,7 +281,27 @@ fn JsonNode(
         }
         Value::Array(arr) => {
             let (is_collapsed, set_collapsed) = signal(false);
+            let pointer = json_search::to_pointer(&path);
+            follow_search(search, pointer.clone(), set_collapsed);
+            let is_built = build_once_expanded(is_collapsed);
             let length = arr.len();
+            let items = StoredValue::new(arr);
+            let path = StoredValue::new(path);
+
+            // Only a window of long arrays is rendered. Move it to show the current search match
+            let (window_start, set_window_start) = signal(0_usize);
+            Effect::new(move |_| {
+                let index = search
+                    .current_match
+                    .with(|c| c.as_deref().and_then(|c| json_search::get_child_index(c, &pointer)));
+                if let Some(index) = index {
+                    let start = index / ARRAY_WINDOW_SIZE * ARRAY_WINDOW_SIZE;
+                    if window_start.get_untracked() != start {
+                        set_window_start.set(start);
+                    }
+                }
+            });
+            let window_end = move || (window_start.get() + ARRAY_WINDOW_SIZE).min(length);
 
             if length == 0 {
                 view! {
@@ -153,29 +326,94 @@ fn JsonNode(
                         </span>
                         <span class="json-brace">"["</span>
                         <div class="json-array-content" class:collapsed=is_collapsed>
-                            {arr
-                                .into_iter()
-                                .enumerate()
-                                .map(|(i, val)| {
-                                    let is_last_item = i == length - 1;
+                            {(length > ARRAY_WINDOW_SIZE)
+                                .then(|| {
                                     view! {
                                         <div
-                                            class="json-array-item"
+                                            class="json-array-window"
                                             style=format!("margin-left: {}px;", (level + 1) * PADDING)
                                         >
-                                            <JsonNode
-                                                value=val
-                                                level=level + 1
-                                                is_last=is_last_item
-                                                collapsed=collapsed
-                                                key=None
-                                                set_selected_object_id=set_selected_object_id
-                                                set_rhs_mode=set_rhs_mode
-                                            />
+                                            <button
+                                                type="button"
+                                                disabled=move || window_start.get() == 0
+                                                on:click=move |_| {
+                                                    set_window_start
+                                                        .update(|s| *s = s.saturating_sub(ARRAY_WINDOW_SIZE))
+                                                }
+                                            >
+                                                "Previous"
+                                            </button>
+                                            <span>
+                                                {move || {
+                                                    format!(
+                                                        "Items {} - {} of {}",
+                                                        window_start.get() + 1,
+                                                        window_end(),
+                                                        length,
+                                                    )
+                                                }}
+                                            </span>
+                                            <button
+                                                type="button"
+                                                disabled=move || window_end() == length
+                                                on:click=move |_| {
+                                                    set_window_start.update(|s| *s += ARRAY_WINDOW_SIZE)
+                                                }
+                                            >
+                                                "Next"
+                                            </button>
                                         </div>
                                     }
-                                })
-                                .collect_view()}
+                                })}
+                            {move || {
+                                is_built
+                                    .get()
+                                    .then(|| {
+                                        let start = window_start.get();
+                                        items
+                                            .with_value(|items| {
+                                                items[start..window_end()].to_vec()
+                                            })
+                                            .into_iter()
+                                            .enumerate()
+                                            .map(|(i, val)| {
+                                                let i = start + i;
+                                                let is_last_item = i == length - 1;
+                                                let mut child_path = path.get_value();
+                                                child_path.push(i.to_string());
+                                                let (node_ref, is_match, is_current) = search_match(
+                                                    search,
+                                                    json_search::to_pointer(&child_path),
+                                                );
+                                                view! {
+                                                    <div
+                                                        class="json-array-item"
+                                                        class:json-match=is_match
+                                                        class:json-match-current=is_current
+                                                        node_ref=node_ref
+                                                        style=format!(
+                                                            "margin-left: {}px;",
+                                                            (level + 1) * PADDING,
+                                                        )
+                                                    >
+                                                        <JsonNode
+                                                            value=val
+                                                            level=level + 1
+                                                            is_last=is_last_item
+                                                            collapsed=collapsed
+                                                            key=None
+                                                            path=child_path
+                                                            path_actions=path_actions
+                                                            search=search
+                                                            set_selected_object_id=set_selected_object_id
+                                                            set_rhs_mode=set_rhs_mode
+                                                        />
+                                                    </div>
+                                                }
+                                            })
+                                            .collect_view()
+                                    })
+                            }}
                             <div style=format!("margin-left: {}px;", level * 20)>
                                 <span class="json-brace">"]"</span>
                                 {if !is_last {
@@ -200,14 +438,19 @@ fn JsonNode(
                                 view! {
                                     <span class="json-string-content, tooltip">
                                         {s.clone()}
-                                        <span
-                                            class="tooltiptext"
-                                            on:click=move |_| {
+                                        <span class="tooltiptext">
+                                            <span on:click={
+                                                let s = s.clone();
+                                                move |_| {
+                                                    set_selected_object_id.update(|c| *c = s.clone());
+                                                    set_rhs_mode.update(|m| *m = RHSMode::Rel(s.clone()));
+                                                }
+                                            }>"Check relationship"</span>
+                                            " | "
+                                            <span on:click=move |_| {
                                                 set_selected_object_id.update(|c| *c = s.clone());
-                                                set_rhs_mode.update(|m| *m = RHSMode::Rel(s.clone()));
-                                            }
-                                        >
-                                            Check relationship
+                                                set_rhs_mode.update(|m| *m = RHSMode::ElementDetail);
+                                            }>"Detail"</span>
                                         </span>
                                     </span>
                                 }
@@ -256,53 +499,85 @@ fn JsonNode(
     }
 }
 
-#[component]
-pub fn JsonNotFound() -> impl IntoView {
-    view! { <span class="json-error">"No data or querying in progress."</span> }
+// Expand or collapse a node on expand all / collapse all, and expand it when it contains a match.
+// Nodes built after an expand all / collapse all follow it
+fn follow_search(search: SearchState, pointer: String, set_collapsed: WriteSignal<bool>) {
+    Effect::new(move |_| {
+        let (generation, is_expanded) = search.expand_command.get();
+        if generation > 0 {
+            set_collapsed.set(!is_expanded);
+        }
+    });
+    Effect::new(move |_| {
+        if search.ancestors.with(|a| a.contains(&pointer)) {
+            set_collapsed.set(false);
+        }
+    });
 }
 
-#[component]
-pub fn RenderStats(json_value: Memo<Option<Value>>) -> impl IntoView {
-    view! {
-        {move || {
-            let stats = match json_value.get(){
-                Some(v) => get_value_number_stats(&v),
-                None => {
-                    None
-                },
-            };
+// Children are built on first expand and kept afterwards
+fn build_once_expanded(is_collapsed: ReadSignal<bool>) -> Memo<bool> {
+    Memo::new(move |was_built: Option<&bool>| {
+        was_built.copied().unwrap_or(false) || !is_collapsed.get()
+    })
+}
 
-            // Render stats
-            if let Some(stats) = stats {
-                view! { <div>"Stats: " {stats}</div> }.into_any()
-            } else {
-                view! { <div>"Stats: N.A."</div> }.into_any()
-            }
+// Highlight of a node matching the search, scrolled into view when it is the current match
+fn search_match(
+    search: SearchState,
+    pointer: String,
+) -> (NodeRef<Div>, Signal<bool>, Signal<bool>) {
+    let node_ref = NodeRef::<Div>::new();
+    let match_pointer = pointer.clone();
+    let is_match = Signal::derive(move || search.matches.with(|m| m.contains(&match_pointer)));
+    let is_current =
+        Signal::derive(move || search.current_match.with(|c| c.as_ref() == Some(&pointer)));
+
+    Effect::new(move |_| {
+        if is_current.get() {
+            // Wait for the ancestors to be expanded
+            request_animation_frame(move || {
+                if let Some(el) = node_ref.get_untracked() {
+                    el.scroll_into_view();
+                }
+            });
         }
-    }}
+    });
 
+    (node_ref, is_match, is_current)
 }
 
-// /metrics/areaMetrics/IPMS1/geometric/value
-fn get_value_number_stats(value: &Value) -> Option<String> {
-    let array = value.as_array()?;
-    let numbers: Vec<f64> = array
-        .iter()
-        .filter_map(|element| element.as_number()?.as_f64())
-        .collect();
-
-    if numbers.is_empty() || numbers.len() != array.len() {
-        log!("Number is empty");
-        return None;
+#[component]
+fn JsonKey(
+    key: String,
+    facet_pointer: Option<(String, String)>,
+    path_actions: PathActions,
+) -> impl IntoView {
+    match facet_pointer {
+        Some((facet_type, pointer)) => {
+            let copy_pointer = pointer.clone();
+            let title = format!("{} {}", facet_type, pointer);
+            view! {
+                <span class="json-key-text tooltip" title=title>
+                    {key}
+                    <span class="tooltiptext json-key-actions">
+                        <span on:click=move |_| path_actions.copy.run(copy_pointer.clone())>
+                            "Copy pointer"
+                        </span>
+                        " | "
+                        <span on:click=move |_| {
+                            path_actions.query.run((facet_type.clone(), pointer.clone()))
+                        }>"Query this path"</span>
+                    </span>
+                </span>
+            }
+                .into_any()
+        }
+        None => view! { <span class="json-key-text">{key}</span> }.into_any(),
     }
+}
 
-    let sum: f64 = numbers.iter().sum();
-    let avg = sum as f64 / numbers.len() as f64;
-
-    let stats = json!({
-        "Sum": sum,
-        "Average": avg,
-    });
-
-    Some(stats.to_string())
+#[component]
+pub fn JsonNotFound() -> impl IntoView {
+    view! { <span class="json-error">"No data or querying in progress."</span> }
 }
@@ -0,0 +1,287 @@
+use leptos::prelude::*;
+
+use crate::model::graph_layout::get_type_color;
+use crate::model::meta_graph::{MetaEdge, MetaGraph};
+use crate::model::model_error::ModelError;
+
+// Busiest edges drawn, the table lists them all
+const MAX_DIAGRAM_EDGES: usize = 40;
+const DIAGRAM_RADIUS: f64 = 220.0;
+const DIAGRAM_MARGIN: f64 = 90.0;
+const TYPE_RADIUS: f64 = 14.0;
+
+#[component]
+pub fn MetaGraphViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+) -> impl IntoView {
+    let (filter, set_filter) = signal(String::new());
+
+    let async_meta_graph = Resource::new(
+        move || (model_id.get(), selected_version.get()),
+        |(model_id, version)| async move { get_meta_graph(model_id, version).await },
+    );
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Type Schema</h2>
+            <div class="search-viewer-input">
+                <input
+                    type="text"
+                    placeholder="Filter by element or relationship type"
+                    prop:value=filter
+                    on:input=move |ev| set_filter.set(event_target_value(&ev))
+                />
+            </div>
+            <Transition fallback=move || view! { <p>"Loading type schema..."</p> }>
+                {move || match async_meta_graph.get() {
+                    Some(Ok(meta_graph)) => {
+                        let text = filter.get().to_lowercase();
+                        let edges: Vec<MetaEdge> = meta_graph
+                            .edges
+                            .iter()
+                            .filter(|e| is_edge_match(e, &text))
+                            .cloned()
+                            .collect();
+                        let filtered = MetaGraph {
+                            edges,
+                            type_counts: meta_graph.type_counts.clone(),
+                        };
+                        view! {
+                            <MetaGraphDiagram meta_graph=filtered.clone() />
+                            <MetaGraphTable meta_graph=filtered />
+                        }
+                            .into_any()
+                    }
+                    Some(Err(e)) => {
+                        view! { <span class="json-error">{format!("Error: {}", e)}</span> }
+                            .into_any()
+                    }
+                    None => view! { <span>"Loading ... "</span> }.into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+fn is_edge_match(edge: &MetaEdge, text: &str) -> bool {
+    text.is_empty()
+        || [
+            &edge.source_type,
+            &edge.relationship_type,
+            &edge.target_type,
+        ]
+        .iter()
+        .any(|t| t.to_lowercase().contains(text))
+}
+
+#[component]
+fn MetaGraphDiagram(meta_graph: MetaGraph) -> impl IntoView {
+    let mut meta_graph = meta_graph;
+    meta_graph.edges.truncate(MAX_DIAGRAM_EDGES);
+    let nodes = meta_graph.get_circle_layout(DIAGRAM_RADIUS);
+    let size = 2.0 * (DIAGRAM_RADIUS + DIAGRAM_MARGIN);
+    let position = |type_: &str| {
+        nodes
+            .iter()
+            .find(|n| n.type_ == type_)
+            .map_or((0.0, 0.0), |n| (n.x + DIAGRAM_MARGIN, n.y + DIAGRAM_MARGIN))
+    };
+    let center = DIAGRAM_RADIUS + DIAGRAM_MARGIN;
+
+    let edges = meta_graph
+        .edges
+        .iter()
+        .enumerate()
+        .map(|(i, edge)| {
+            let (x1, y1) = position(&edge.source_type);
+            let (x2, y2) = position(&edge.target_type);
+            let label = format!("{}: {}", edge.relationship_type, edge.count);
+            // Self loop drawn outside the circle, other edges bent so parallel ones stay apart
+            let (path, label_x, label_y) = match edge.source_type == edge.target_type {
+                true => {
+                    let (dx, dy) = (x1 - center, y1 - center);
+                    let length = (dx * dx + dy * dy).sqrt().max(1.0);
+                    let (ux, uy) = (dx / length, dy / length);
+                    let (cx, cy) = (x1 + ux * 60.0, y1 + uy * 60.0);
+                    (
+                        format!(
+                            "M {} {} C {} {} {} {} {} {}",
+                            x1,
+                            y1,
+                            cx - uy * 40.0,
+                            cy + ux * 40.0,
+                            cx + uy * 40.0,
+                            cy - ux * 40.0,
+                            x1,
+                            y1
+                        ),
+                        cx,
+                        cy,
+                    )
+                }
+                false => {
+                    let bend = 20.0 + (i % 4) as f64 * 15.0;
+                    let (mx, my) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+                    let (dx, dy) = (x2 - x1, y2 - y1);
+                    let length = (dx * dx + dy * dy).sqrt().max(1.0);
+                    let (cx, cy) = (mx - dy / length * bend, my + dx / length * bend);
+                    (
+                        format!("M {} {} Q {} {} {} {}", x1, y1, cx, cy, x2, y2),
+                        cx,
+                        cy,
+                    )
+                }
+            };
+            view! {
+                <g class="meta-graph-edge">
+                    <path d=path marker-end="url(#meta-graph-arrow)">
+                    </path>
+                    <text x=label_x y=label_y text-anchor="middle">
+                        {label}
+                    </text>
+                </g>
+            }
+        })
+        .collect_view();
+
+    let type_nodes = nodes
+        .iter()
+        .map(|node| {
+            let (x, y) = (node.x + DIAGRAM_MARGIN, node.y + DIAGRAM_MARGIN);
+            let label = format!(
+                "{} ({})",
+                node.type_,
+                meta_graph.get_type_count(&node.type_)
+            );
+            view! {
+                <g class="graph-viewer-node">
+                    <circle cx=x cy=y r=TYPE_RADIUS fill=get_type_color(&node.type_) />
+                    <text x=x y=y + TYPE_RADIUS + 14.0 text-anchor="middle">
+                        {label}
+                    </text>
+                </g>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <svg class="meta-graph" viewBox=format!("0 0 {} {}", size, size)>
+            <defs>
+                <marker
+                    id="meta-graph-arrow"
+                    viewBox="0 0 10 10"
+                    refX="24"
+                    refY="5"
+                    markerWidth="6"
+                    markerHeight="6"
+                    orient="auto"
+                >
+                    <path d="M 0 0 L 10 5 L 0 10 z" />
+                </marker>
+            </defs>
+            {edges}
+            {type_nodes}
+        </svg>
+    }
+}
+
+fn get_edge_label(edge: &MetaEdge) -> String {
+    format!(
+        "{} -[{}]-> {}: {}",
+        edge.source_type, edge.relationship_type, edge.target_type, edge.count
+    )
+}
+
+#[component]
+fn MetaGraphTable(meta_graph: MetaGraph) -> impl IntoView {
+    view! {
+        <div class="aggregation-table">
+            <table>
+                <tr>
+                    <th>"Source type"</th>
+                    <th>"Relationship"</th>
+                    <th>"Target type"</th>
+                    <th>"Count"</th>
+                    <th title="Source elements with this relationship / all of the source type">
+                        "Sources"
+                    </th>
+                    <th>"Min out"</th>
+                    <th>"Max out"</th>
+                    <th>"Avg out"</th>
+                </tr>
+                {meta_graph
+                    .edges
+                    .iter()
+                    .map(|edge| {
+                        view! {
+                            <tr title=get_edge_label(edge)>
+                                <td>{edge.source_type.clone()}</td>
+                                <td>{edge.relationship_type.clone()}</td>
+                                <td>{edge.target_type.clone()}</td>
+                                <td>{edge.count}</td>
+                                <td>
+                                    {edge.source_count} " / "
+                                    {meta_graph.get_type_count(&edge.source_type)}
+                                </td>
+                                <td>{edge.min_out_degree}</td>
+                                <td>{edge.max_out_degree}</td>
+                                <td>{format!("{:.2}", edge.avg_out_degree)}</td>
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+            </table>
+        </div>
+    }
+}
+
+#[server(GetMetaGraph, "/api")]
+pub async fn get_meta_graph(model_id: String, version: String) -> Result<MetaGraph, ModelError> {
+    use crate::model::cubs_model::CusObject;
+    use crate::model::model_dict::generate_element_count_by;
+    use crate::model::{app_state, parser};
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    if model_id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    println!("[MetaGraphViewer] get_meta_graph for model: {}", model_id);
+    let start_time = Instant::now();
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
+    let graph = parser::read_model_graph(&app_state, &model_id, &model).await?;
+
+    let type_counts: HashMap<String, usize> =
+        generate_element_count_by(&model.elements, |e| e.get_type())
+            .unwrap_or_default()
+            .value
+            .into_iter()
+            .map(|c| (c.element, c.count as usize))
+            .collect();
+
+    // Connected relationships with the types at both ends
+    let type_of = |id: &str| model.get_element_with_id(id).map(|e| e.type_.as_str());
+    let mut relationships: Vec<(&str, &str, &str, &str)> = Vec::new();
+    for connector in graph.get_connectors() {
+        let source_id = connector.get_element_id();
+        for path in connector.get_out_id() {
+            let relationship = model.get_relationship_with_id(&path.0);
+            if let (Some(source_type), Some(relationship), Some(target_type)) =
+                (type_of(source_id), relationship, type_of(&path.1))
+            {
+                relationships.push((source_id, source_type, &relationship.type_, target_type));
+            }
+        }
+    }
+    let meta_graph = MetaGraph::from(type_counts, &relationships);
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!("[Execution time] get_meta_graph - {:?}", elapsed_time);
+
+    Ok(meta_graph)
+}
@@ -2,4 +2,19 @@ pub mod json_viewer;
 pub mod model_stats_viewer;
 pub mod element_viewer;
 pub mod status_toast_viewer;
-pub mod relationship_viewer;
\ No newline at end of file
+pub mod relationship_viewer;
+pub mod cache_admin_viewer;
+pub mod stats_viewer;
+pub mod value_breakdown_viewer;
+pub mod facet_schema_viewer;
+pub mod table_viewer;
+pub mod aggregation_viewer;
+pub mod search_viewer;
+pub mod saved_query_viewer;
+pub mod element_detail_viewer;
+pub mod compare_viewer;
+pub mod graph_viewer;
+pub mod relationship_tree_viewer;
+pub mod containment_viewer;
+pub mod meta_graph_viewer;
+pub mod impact_viewer;
\ No newline at end of file
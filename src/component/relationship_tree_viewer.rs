@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::model::cubs_model::RelationshipDirection;
+use crate::model::model_error::ModelError;
+use crate::model::relationship_tree::{
+    get_level_key, get_tree_path, is_in_path, RelationshipTree, TreeNode,
+};
+
+// Signals shared by every branch of the tree
+#[derive(Clone, Copy)]
+struct TreeState {
+    tree: RwSignal<RelationshipTree>,
+    // Level keys being fetched
+    loading: RwSignal<HashSet<String>>,
+    // Tree paths of the expanded nodes
+    expanded: RwSignal<HashSet<String>>,
+    load_level: Callback<(String, RelationshipDirection)>,
+    set_selected_object_id: WriteSignal<String>,
+}
+
+#[component]
+pub fn RelationshipTreeViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    selected_object_id: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
+) -> impl IntoView {
+    let tree = RwSignal::new(RelationshipTree::default());
+    let loading = RwSignal::new(HashSet::<String>::new());
+    let expanded = RwSignal::new(HashSet::<String>::new());
+    let (error, set_error) = signal(None::<String>);
+
+    // Fetch one level unless loaded or already being fetched
+    let load_level = Callback::new(move |(id, direction): (String, RelationshipDirection)| {
+        let key = get_level_key(&id, &direction);
+        if tree.with_untracked(|t| t.is_loaded(&id, &direction))
+            || loading.with_untracked(|l| l.contains(&key))
+        {
+            return;
+        }
+        loading.update(|l| {
+            l.insert(key.clone());
+        });
+        let (model, version) = (model_id.get_untracked(), selected_version.get_untracked());
+        spawn_local(async move {
+            let result =
+                get_relationship_level(model.clone(), version.clone(), id.clone(), direction).await;
+            loading.update(|l| {
+                l.remove(&key);
+            });
+            // Drop levels of a model version no longer selected
+            if model != model_id.get_untracked() || version != selected_version.get_untracked() {
+                return;
+            }
+            match result {
+                Ok(nodes) => {
+                    set_error.set(None);
+                    tree.update(|t| t.insert_level(&id, &direction, nodes));
+                }
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+        });
+    });
+
+    // Loaded levels belong to one model version, kept while the selected element changes
+    let loaded_version = StoredValue::new((String::new(), String::new()));
+    Effect::new(move |_| {
+        let version = (model_id.get(), selected_version.get());
+        if loaded_version.with_value(|v| *v != version) {
+            loaded_version.set_value(version);
+            tree.update(|t| t.clear());
+            expanded.update(|e| e.clear());
+        }
+        let id = selected_object_id.get();
+        if !id.is_empty() {
+            load_level.run((id.clone(), RelationshipDirection::Parent));
+            load_level.run((id, RelationshipDirection::Child));
+        }
+    });
+
+    let state = TreeState {
+        tree,
+        loading,
+        expanded,
+        load_level,
+        set_selected_object_id,
+    };
+
+    view! {
+        {move || error.get().map(|e| view! { <span class="json-error">{format!("Error: {}", e)}</span> })}
+        {move || {
+            let id = selected_object_id.get();
+            view! {
+                <h3 class="label-model-stats">"Parents: "</h3>
+                <TreeBranch
+                    state=state
+                    id=id.clone()
+                    path=id.clone()
+                    direction=RelationshipDirection::Parent
+                />
+                <h3 class="label-model-stats">"Childs: "</h3>
+                <TreeBranch
+                    state=state
+                    id=id.clone()
+                    path=id
+                    direction=RelationshipDirection::Child
+                />
+            }
+        }}
+    }
+}
+
+// Connections of an element in one direction
+#[component]
+fn TreeBranch(
+    state: TreeState,
+    id: String,
+    path: String,
+    direction: RelationshipDirection,
+) -> AnyView {
+    let key = get_level_key(&id, &direction);
+    let level = Memo::new(move |_| state.tree.with(|t| t.get_level(&id, &direction).cloned()));
+
+    view! {
+        {move || match level.get() {
+            Some(nodes) if nodes.is_empty() => {
+                view! { <div class="relationship-tree-empty">"None"</div> }.into_any()
+            }
+            Some(nodes) => {
+                view! {
+                    <ul class="relationship-tree">
+                        {nodes
+                            .into_iter()
+                            .map(|node| {
+                                view! {
+                                    <TreeNodeRender
+                                        state=state
+                                        node=node
+                                        path=path.clone()
+                                        direction=direction
+                                    />
+                                }
+                            })
+                            .collect_view()}
+                    </ul>
+                }
+                    .into_any()
+            }
+            None if state.loading.with(|l| l.contains(&key)) => {
+                view! { <div class="relationship-tree-empty">"Loading ... "</div> }.into_any()
+            }
+            None => ().into_any(),
+        }}
+    }
+    .into_any()
+}
+
+#[component]
+fn TreeNodeRender(
+    state: TreeState,
+    node: TreeNode,
+    path: String,
+    direction: RelationshipDirection,
+) -> impl IntoView {
+    let relationship = node.relationship;
+    let id = relationship.counterpart_id.clone();
+    let node_path = get_tree_path(&path, &id);
+    let is_cycle = is_in_path(&path, &id);
+    let can_expand = node.child_count > 0 && !is_cycle;
+
+    let is_expanded = {
+        let node_path = node_path.clone();
+        Memo::new(move |_| state.expanded.with(|e| e.contains(&node_path)))
+    };
+    let toggle = {
+        let (id, node_path) = (id.clone(), node_path.clone());
+        move |_| {
+            if is_expanded.get_untracked() {
+                state.expanded.update(|e| {
+                    e.remove(&node_path);
+                });
+            } else {
+                state.load_level.run((id.clone(), direction));
+                state.expanded.update(|e| {
+                    e.insert(node_path.clone());
+                });
+            }
+        }
+    };
+    let arrow = match direction {
+        RelationshipDirection::Parent => "➚",
+        RelationshipDirection::Child => "➘",
+    };
+    let name = match relationship.counterpart_name.is_empty() {
+        true => relationship.counterpart_id.clone(),
+        false => relationship.counterpart_name,
+    };
+    let select_id = id.clone();
+
+    view! {
+        <li>
+            <div class="relationship-tree-row">
+                <button
+                    type="button"
+                    class="relationship-tree-toggle"
+                    disabled=!can_expand
+                    on:click=toggle
+                >
+                    {move || match (can_expand, is_expanded.get()) {
+                        (false, _) => "•",
+                        (true, true) => "▾",
+                        (true, false) => "▸",
+                    }}
+                </button>
+                <span title=relationship.relationship_name>{arrow} " " {relationship.relationship_type}</span>
+                <span
+                    class="relationship-tree-element"
+                    title="Center the tree on this element"
+                    on:click=move |_| state.set_selected_object_id.set(select_id.clone())
+                >
+                    {name}
+                    " : "
+                    {relationship.counterpart_type}
+                </span>
+                {(node.child_count > 0)
+                    .then(|| view! { <span class="relationship-tree-count">"(" {node.child_count} ")"</span> })}
+                {is_cycle.then(|| view! { <span class="relationship-tree-count">"(cycle)"</span> })}
+            </div>
+            {move || {
+                is_expanded
+                    .get()
+                    .then(|| {
+                        view! {
+                            <TreeBranch
+                                state=state
+                                id=id.clone()
+                                path=node_path.clone()
+                                direction=direction
+                            />
+                        }
+                    })
+            }}
+        </li>
+    }
+}
+
+#[server(GetRelationshipLevel, "/api")]
+pub async fn get_relationship_level(
+    model_id: String,
+    version: String,
+    id: String,
+    direction: RelationshipDirection,
+) -> Result<Vec<TreeNode>, ModelError> {
+    use crate::model::element_detail::RelationshipRow;
+    use crate::model::{app_state, parser};
+    use std::time::Instant;
+
+    if model_id.is_empty() || id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    println!(
+        "[RelationshipTreeViewer] get_relationship_level for model: {}, cubsobject id: {}",
+        model_id, id
+    );
+    let start_time = Instant::now();
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
+    let graph = parser::read_model_graph(&app_state, &model_id, &model).await?;
+    let connector = graph
+        .get_connection(&id)
+        .ok_or(ModelError::ElementNotFound(id.clone()))?;
+
+    let paths = match direction {
+        RelationshipDirection::Parent => connector.get_in_id(),
+        RelationshipDirection::Child => connector.get_out_id(),
+    };
+    let mut nodes: Vec<TreeNode> = paths
+        .into_iter()
+        .map(|path| {
+            // Connections of the counterpart in the same direction, expandable when not empty
+            let child_count = graph
+                .get_connection(&path.1)
+                .map_or(0, |c| match direction {
+                    RelationshipDirection::Parent => c.get_in_id().len(),
+                    RelationshipDirection::Child => c.get_out_id().len(),
+                });
+            TreeNode {
+                relationship: RelationshipRow::from(&model, &path.0, &path.1),
+                child_count,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| {
+        (
+            &a.relationship.relationship_type,
+            &a.relationship.counterpart_name,
+        )
+            .cmp(&(
+                &b.relationship.relationship_type,
+                &b.relationship.counterpart_name,
+            ))
+    });
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!(
+        "[Execution time] get_relationship_level - {:?}",
+        elapsed_time
+    );
+
+    Ok(nodes)
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::component::relationship_tree_viewer::RelationshipTreeViewer;
+pub use crate::model::cubs_model::RelationshipDirection;
 use crate::model::model_error::ModelError;
 use leptos::{logging::log, prelude::*};
 use serde::{Deserialize, Serialize};
@@ -9,6 +11,8 @@ use serde_json::Value;
 pub enum OutputToken<T> {
     Tab,
     Value(T),
+    // Element shown in full elsewhere in the tree, its subtree is not repeated
+    Reference(T),
     InArrow,
     OutArrow,
 }
@@ -23,11 +27,15 @@ pub struct OutputGraph<T> {
     pub parent_lines: Vec<OutputLine<T>>,
     pub child_lines: Vec<OutputLine<T>>,
     pub elements_data: HashMap<String, Value>,
+    pub parent_count: PathCount,
+    pub child_count: PathCount,
 }
 
-pub enum RelationshipDirection {
-    Parent,
-    Child,
+// Distinct elements printed against the paths a full expansion would print
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct PathCount {
+    pub distinct: usize,
+    pub total: u64,
 }
 
 impl<T> OutputLine<T> {
@@ -46,6 +54,61 @@ pub fn RelationshipViewer(
     selected_version: ReadSignal<String>,
     selected_object_id: ReadSignal<String>,
     set_selected_object_id: WriteSignal<String>,
+) -> impl IntoView {
+    // Tree expands one level at a time, outline prints the fixed levels of build_output
+    let (is_outline, set_is_outline) = signal(false);
+
+    view! {
+        <h2 class="label-model-stats">Relationship Stats</h2>
+        <div class="relationship-viewer-mode">
+            <button
+                type="button"
+                class:relationship-viewer-mode-active=move || !is_outline.get()
+                on:click=move |_| set_is_outline.set(false)
+            >
+                "Tree"
+            </button>
+            <button
+                type="button"
+                class:relationship-viewer-mode-active=move || is_outline.get()
+                on:click=move |_| set_is_outline.set(true)
+            >
+                "Outline"
+            </button>
+        </div>
+        {move || match is_outline.get() {
+            false => {
+                view! {
+                    <RelationshipTreeViewer
+                        model_id=model_id
+                        selected_version=selected_version
+                        selected_object_id=selected_object_id
+                        set_selected_object_id=set_selected_object_id
+                    />
+                }
+                    .into_any()
+            }
+            true => {
+                view! {
+                    <RelationshipOutline
+                        model_id=model_id
+                        selected_version=selected_version
+                        selected_object_id=selected_object_id
+                        set_selected_object_id=set_selected_object_id
+                    />
+                }
+                    .into_any()
+            }
+        }}
+    }
+}
+
+#[component]
+fn RelationshipOutline(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    selected_object_id: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
 ) -> impl IntoView {
     // Create a resource of relationship detail
     // Resource trigger the fetcher when id changed
@@ -76,12 +139,9 @@ pub fn RelationshipViewer(
         <Transition fallback=move || {
             view! { <p>"Loading initial data..."</p> }
         }>
-            <h2 class="label-model-stats">Relationship Stats</h2>
             {move || async_relationship_detail_result}
         </Transition>
     }
-
-    // view! { <span>"Relationship viewer for "{async_relationship_detail_result}</span> }
 }
 
 #[component]
@@ -89,8 +149,12 @@ pub fn RelationshipNodeRender(data: OutputGraph<String>, set_selected_object_id:
     let elements_store = data.elements_data;
     let parent_lines = data.parent_lines;
     let child_lines = data.child_lines;
+    let count_label = |count: &PathCount| {
+        format!("{} distinct elements out of {} paths", count.distinct, count.total)
+    };
     view! {
         <h3 class="label-model-stats">"Parents: "</h3>
+        <span class="relationship-viewer-count">{count_label(&data.parent_count)}</span>
         {parent_lines
             .iter()
             .map(|output_line| {
@@ -115,6 +179,7 @@ pub fn RelationshipNodeRender(data: OutputGraph<String>, set_selected_object_id:
             .collect::<Vec<_>>()}
 
         <h3 class="label-model-stats">"Childs: "</h3>
+        <span class="relationship-viewer-count">{count_label(&data.child_count)}</span>
         {child_lines
             .iter()
             .map(|output_line| {
@@ -166,6 +231,21 @@ pub fn  OutputTokenRender<'a>(token: OutputToken<String>, elements_store: &'a Ha
                 </span>
             }.into_any()    
         },
+        OutputToken::Reference(v) => {
+            let element = elements_store.get(&v).cloned().unwrap_or(Value::Null);
+            view! {
+                <span
+                    class="relationship-viewer-flex-line relationship-viewer-reference"
+                    title="Shown in full elsewhere in this tree, its relationships are not repeated here"
+                    on:click=move |_| set_selected_object_id.set(v.clone())
+                >
+                    <RenderJsonValue value=element.clone() key="name".to_string() />
+                    :
+                    <RenderJsonValue value=element key="type".to_string() />
+                    " ↑"
+                </span>
+            }.into_any()
+        },
 
         OutputToken::InArrow => view! { <span class="relationship-viewer-flex-line">"➚"</span> }.into_any(),
         OutputToken::OutArrow => view! { <span class="relationship-viewer-flex-line">"➘"</span> }.into_any(),
@@ -250,15 +330,8 @@ pub async fn get_relationship_detail(
     id: String,
 ) -> Result<OutputGraph<String>, ModelError> {
     use crate::model::{
-        app_state,
-        element_graph::{ElementConnector, ElementGraph},
-        element_graph_parser::ElementGraphParser,
-        element_parser::ElementConnectorBuilder,
-        model_error::ModelError,
+        app_state, element_graph_parser::ElementGraphParser, model_error::ModelError, parser,
     };
-    use actix_web::web::Data;
-    use leptos::logging::log;
-    use leptos_actix::*;
     use std::time::Instant;
 
     // Validate input
@@ -274,39 +347,11 @@ pub async fn get_relationship_detail(
     let start_time = Instant::now();
 
     // Get app state
-    let app_state: Data<app_state::AppState> = extract()
-        .await
-        .map_err(|_| ModelError::ModelNotFound(model_id.clone()))?;
-    log!("App state -> {:?}", app_state);
-
-    // Get model
-    let model = app_state
-        .get_model_cache()
-        .get(&model_id, &version)
-        .ok_or(ModelError::ModelNotFound(model_id.clone()))?;
-    let elements = &model.elements;
-    let relationship = &model.relationships;
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
 
     // Get graph
-    let graph_cache = app_state.get_graph_cache();
-    let graph = match graph_cache.get(&model_id, &version) {
-        Some(graph) => {
-            println!("[RelationshipViewer - get_relationship_detail]: Found graph in cache");
-            graph
-        }
-        None => {
-            println!(
-                "[RelationshipViewer - get_relationship_detail]: Graph not found cache. Building.."
-            );
-            // Build graph if not found
-            let built_graph = ElementConnectorBuilder::build_graph(elements, relationship)?;
-
-            // Add to cache
-            graph_cache.insert(&model_id, &version, &built_graph);
-
-            built_graph
-        }
-    };
+    let graph = parser::read_model_graph(&app_state, &model_id, &model).await?;
 
     // From graph parse relationship and return part of the graph n parent and n layer of child
     let parse_graph = ElementGraphParser::parse_graph(&graph, &id, 1, 2)?;
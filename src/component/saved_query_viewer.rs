@@ -0,0 +1,209 @@
+use leptos::prelude::*;
+
+use crate::model::saved_query::SavedQuery;
+
+#[component]
+pub fn SavedQueryPicker(
+    // Saved query of the current form values
+    current: Signal<SavedQuery>,
+    on_load: Callback<SavedQuery>,
+    on_run: Callback<SavedQuery>,
+) -> impl IntoView {
+    let (selected_id, set_selected_id) = signal(None::<i32>);
+    let (name, set_name) = signal(String::new());
+    let (error, set_error) = signal(None::<String>);
+
+    let save_action = Action::new(move |query: &SavedQuery| {
+        let query = query.clone();
+        async move { create_saved_query(query).await }
+    });
+    let delete_action = Action::new(move |id: &i32| {
+        let id = *id;
+        async move { delete_saved_query(id).await }
+    });
+
+    // Reload the list after each change
+    let saved_queries = Resource::new(
+        move || (save_action.version().get(), delete_action.version().get()),
+        |_| async move { list_saved_queries().await },
+    );
+
+    Effect::new(move |_| {
+        if let Some(result) = save_action.value().get() {
+            match result {
+                Ok(query) => {
+                    set_selected_id.set(Some(query.id));
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+        }
+    });
+    Effect::new(move |_| {
+        if let Some(Err(e)) = delete_action.value().get() {
+            set_error.set(Some(e.to_string()));
+        }
+    });
+
+    let selected_query = move || {
+        let id = selected_id.get()?;
+        saved_queries
+            .get()
+            .and_then(|r| r.ok())
+            .and_then(|queries| queries.into_iter().find(|q| q.id == id))
+    };
+
+    view! {
+        <div class="flex-container-view-input-row">
+            <label for="saved_query">Saved query :</label>
+            <select
+                id="saved_query"
+                on:change=move |ev| {
+                    set_selected_id.set(event_target_value(&ev).parse().ok());
+                }
+            >
+                <option value="">"-"</option>
+                <Transition>
+                    {move || {
+                        saved_queries
+                            .get()
+                            .and_then(|r| r.ok())
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|query| {
+                                let is_selected = move || selected_id.get() == Some(query.id);
+                                view! {
+                                    <option value=query.id selected=is_selected>
+                                        {query.name}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </Transition>
+            </select>
+            <button
+                type="button"
+                title="Fill the form with the saved query"
+                on:click=move |_| {
+                    if let Some(query) = selected_query() {
+                        set_name.set(query.name.clone());
+                        on_load.run(query);
+                    }
+                }
+            >
+                "Load"
+            </button>
+            <button
+                type="button"
+                title="Run the saved query against the selected model version"
+                on:click=move |_| {
+                    if let Some(query) = selected_query() {
+                        on_load.run(query.clone());
+                        on_run.run(query);
+                    }
+                }
+            >
+                "Run"
+            </button>
+            <button
+                type="button"
+                on:click=move |_| {
+                    if let Some(id) = selected_id.get_untracked() {
+                        set_selected_id.set(None);
+                        delete_action.dispatch(id);
+                    }
+                }
+            >
+                "Delete"
+            </button>
+
+            <input
+                type="text"
+                placeholder="Saved query name"
+                prop:value=name
+                on:input=move |ev| set_name.set(event_target_value(&ev))
+            />
+            <button
+                type="button"
+                title="Save the current filters, replacing a saved query with the same name"
+                on:click=move |_| {
+                    let query = SavedQuery {
+                        name: name.get_untracked(),
+                        ..current.get_untracked()
+                    };
+                    match query.validate() {
+                        Ok(_) => {
+                            save_action.dispatch(query);
+                        }
+                        Err(e) => set_error.set(Some(e)),
+                    }
+                }
+            >
+                "Save"
+            </button>
+            {move || error.get().map(|e| view! { <span class="pointer-warning">{e}</span> })}
+        </div>
+    }
+}
+
+#[server(CreateSavedQuery, "/api")]
+pub async fn create_saved_query(query: SavedQuery) -> Result<SavedQuery, ServerFnError> {
+    use crate::model::{app_state, saved_query_store};
+    use actix_web::web::Data;
+    use leptos_actix::*;
+
+    query.validate().map_err(ServerFnError::new)?;
+
+    let app_state: Data<app_state::AppState> = extract().await?;
+    let saved = saved_query_store::save_query(app_state.get_pg_pool_ref(), &query)
+        .await
+        .map_err(|e| {
+            eprintln!(
+                "[create_saved_query] Unable to save query {}: {}",
+                query.name, e
+            );
+            ServerFnError::new("Unable to save query")
+        })?;
+
+    println!(
+        "[create_saved_query] Saved query {} with id {}",
+        saved.name, saved.id
+    );
+    Ok(saved)
+}
+
+#[server(ListSavedQueries, "/api")]
+pub async fn list_saved_queries() -> Result<Vec<SavedQuery>, ServerFnError> {
+    use crate::model::{app_state, saved_query_store};
+    use actix_web::web::Data;
+    use leptos_actix::*;
+
+    let app_state: Data<app_state::AppState> = extract().await?;
+    saved_query_store::read_saved_queries(app_state.get_pg_pool_ref())
+        .await
+        .map_err(|e| {
+            eprintln!("[list_saved_queries] Unable to read saved queries: {}", e);
+            ServerFnError::new("Unable to read saved queries")
+        })
+}
+
+#[server(DeleteSavedQuery, "/api")]
+pub async fn delete_saved_query(id: i32) -> Result<(), ServerFnError> {
+    use crate::model::{app_state, saved_query_store};
+    use actix_web::web::Data;
+    use leptos_actix::*;
+
+    let app_state: Data<app_state::AppState> = extract().await?;
+    let is_deleted = saved_query_store::delete_saved_query(app_state.get_pg_pool_ref(), id)
+        .await
+        .map_err(|e| {
+            eprintln!("[delete_saved_query] Unable to delete query {}: {}", id, e);
+            ServerFnError::new("Unable to delete saved query")
+        })?;
+
+    if !is_deleted {
+        return Err(ServerFnError::new(format!("Saved query {} not found", id)));
+    }
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use leptos::prelude::*;
+
+use crate::app::RHSMode;
+use crate::model::model_error::ModelError;
+use crate::model::search_index::{SearchHit, SearchResult};
+
+const SEARCH_LIMIT: usize = 100;
+
+#[component]
+pub fn SearchViewer(
+    model_id: ReadSignal<String>,
+    selected_version: ReadSignal<String>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+    on_show_element: Callback<String>,
+) -> impl IntoView {
+    let (search_text, set_search_text) = signal(String::new());
+    let (submitted_text, set_submitted_text) = signal(String::new());
+
+    let async_result = Resource::new(
+        move || (model_id.get(), selected_version.get(), submitted_text.get()),
+        |(model_id, version, text)| async move {
+            if text.trim().is_empty() {
+                return Ok(SearchResult::default());
+            }
+            search_model(model_id, version, text, SEARCH_LIMIT).await
+        },
+    );
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Search</h2>
+            <div class="search-viewer-input">
+                <input
+                    type="text"
+                    placeholder="pump name:main type:valve facets:grundfos"
+                    prop:value=search_text
+                    on:input=move |ev| set_search_text.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            ev.prevent_default();
+                            set_submitted_text.set(search_text.get_untracked());
+                        }
+                    }
+                />
+                <button
+                    type="button"
+                    on:click=move |_| set_submitted_text.set(search_text.get_untracked())
+                >
+                    "Search"
+                </button>
+            </div>
+            <Transition fallback=move || view! { <p>"Searching..."</p> }>
+                {move || match async_result.get() {
+                    Some(Ok(result)) => {
+                        view! {
+                            <h4 class="label-model-stats">
+                                {result.hits.len()} " out of " {result.total_hit_count} " results"
+                            </h4>
+                            {result
+                                .hits
+                                .into_iter()
+                                .map(|hit| {
+                                    view! {
+                                        <SearchHitRender
+                                            hit=hit
+                                            set_selected_object_id=set_selected_object_id
+                                            set_rhs_mode=set_rhs_mode
+                                            on_show_element=on_show_element
+                                        />
+                                    }
+                                })
+                                .collect_view()}
+                        }
+                            .into_any()
+                    }
+                    Some(Err(e)) => {
+                        view! { <span class="json-error">{format!("Error: {}", e)}</span> }
+                            .into_any()
+                    }
+                    None => view! { <span>"Loading ... "</span> }.into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn SearchHitRender(
+    hit: SearchHit,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+    on_show_element: Callback<String>,
+) -> impl IntoView {
+    let show_id = hit.id.clone();
+    let rel_id = hit.id.clone();
+
+    view! {
+        <div class="search-viewer-hit">
+            <div>
+                <b>{hit.name}</b>
+                " : "
+                {hit.type_}
+                " ("
+                {hit.nature}
+                ")"
+            </div>
+            <div class="search-viewer-hit-matches">
+                {hit.matches.join(", ")} " - score " {hit.score}
+            </div>
+            <button type="button" on:click=move |_| on_show_element.run(show_id.clone())>
+                "Show element"
+            </button>
+            <button
+                type="button"
+                on:click=move |_| {
+                    set_selected_object_id.set(rel_id.clone());
+                    set_rhs_mode.set(RHSMode::Rel(rel_id.clone()));
+                }
+            >
+                "Check Relationship"
+            </button>
+        </div>
+    }
+}
+
+#[server(SearchModel, "/api")]
+pub async fn search_model(
+    model_id: String,
+    version: String,
+    text: String,
+    limit: usize,
+) -> Result<SearchResult, ModelError> {
+    use crate::model::{app_state, parser, search_index::SearchIndex};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    if model_id.is_empty() {
+        return Err(ModelError::InvalidInput);
+    }
+
+    println!(
+        "[SearchViewer] search_model for model: {}, text: {}",
+        model_id, text
+    );
+    let start_time = Instant::now();
+
+    let app_state = app_state::extract_app_state().await?;
+    let model = parser::read_model(&app_state, &model_id, &version).await?;
+
+    // Build the index once for all concurrent requests and add to cache
+    let index = app_state
+        .get_search_cache()
+        .get_or_load(&model_id, &model.version.to_string(), async {
+            Ok::<_, ModelError>(Arc::new(SearchIndex::from(&model)))
+        })
+        .await?;
+
+    let result = index.search(&model, &text, limit);
+
+    //Log time
+    let elapsed_time = start_time.elapsed();
+    println!("[Execution time] search_model - {:?}", elapsed_time);
+
+    Ok(result)
+}
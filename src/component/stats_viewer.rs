@@ -0,0 +1,92 @@
+use leptos::prelude::*;
+
+use crate::model::value_stats::{HistogramBin, NumericStats};
+
+const CHART_WIDTH: f64 = 400.0;
+const CHART_HEIGHT: f64 = 80.0;
+
+#[component]
+pub fn RenderStats(stats: ReadSignal<Option<NumericStats>>) -> impl IntoView {
+    view! {
+        {move || match stats.get() {
+            Some(stats) => view! { <NumericStatsRender stats=stats /> }.into_any(),
+            None => view! { <div>"Stats: N.A."</div> }.into_any(),
+        }}
+    }
+}
+
+#[component]
+fn NumericStatsRender(stats: NumericStats) -> impl IntoView {
+    let percentiles = stats
+        .percentiles
+        .iter()
+        .map(|p| format!("p{}: {}", p.percentile, format_number(p.value)))
+        .collect::<Vec<_>>()
+        .join("  ");
+
+    view! {
+        <div class="stats-viewer">
+            <div class="stats-viewer-summary">
+                <span>"Count: " {stats.count}</span>
+                <span>"Null: " {stats.null_count}</span>
+                <span>"Non numeric: " {stats.non_numeric_count}</span>
+                <span>"Sum: " {format_number(stats.sum)}</span>
+                <span>"Min: " {format_number(stats.min)}</span>
+                <span>"Max: " {format_number(stats.max)}</span>
+                <span>"Mean: " {format_number(stats.mean)}</span>
+                <span>"Median: " {format_number(stats.median)}</span>
+                <span>"Std dev: " {format_number(stats.std_dev)}</span>
+            </div>
+            <div class="stats-viewer-summary">{percentiles}</div>
+            <HistogramRender bins=stats.histogram />
+        </div>
+    }
+}
+
+#[component]
+fn HistogramRender(bins: Vec<HistogramBin>) -> impl IntoView {
+    let max_count = bins.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+    let bar_width = CHART_WIDTH / bins.len().max(1) as f64;
+
+    view! {
+        <svg
+            class="stats-viewer-histogram"
+            width=CHART_WIDTH
+            height=CHART_HEIGHT
+            viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)
+        >
+            {bins
+                .into_iter()
+                .enumerate()
+                .map(|(i, bin)| {
+                    let height = CHART_HEIGHT * bin.count as f64 / max_count as f64;
+                    view! {
+                        <rect
+                            x=i as f64 * bar_width
+                            y=CHART_HEIGHT - height
+                            width=(bar_width - 1.0).max(1.0)
+                            height=height
+                        >
+                            <title>
+                                {format!(
+                                    "[{}, {}]: {}",
+                                    format_number(bin.lower),
+                                    format_number(bin.upper),
+                                    bin.count,
+                                )}
+                            </title>
+                        </rect>
+                    }
+                })
+                .collect_view()}
+        </svg>
+    }
+}
+
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{:.3}", n)
+    }
+}
@@ -0,0 +1,174 @@
+use leptos::prelude::*;
+use leptos_use::{use_clipboard, UseClipboardReturn};
+
+use crate::app::RHSMode;
+use crate::model::element_table::{compare_cells, ElementTable};
+
+#[component]
+pub fn TableViewer(
+    table: ReadSignal<Option<ElementTable>>,
+    set_selected_object_id: WriteSignal<String>,
+    set_rhs_mode: WriteSignal<RHSMode>,
+) -> impl IntoView {
+    let UseClipboardReturn { copy, copied, .. } = use_clipboard();
+    let (sort_column, set_sort_column) = signal(None::<usize>);
+    let (is_ascending, set_ascending) = signal(true);
+    let (filters, set_filters) = signal(Vec::<String>::new());
+
+    // Reset sorting and filters for a new table
+    Effect::new(move |_| {
+        let column_count = table.with(|t| t.as_ref().map_or(0, |t| t.get_headers().len()));
+        set_sort_column.set(None);
+        set_filters.set(vec![String::new(); column_count]);
+    });
+
+    // Toggle direction when sorting the same column again
+    let sort_by = move |column: usize| {
+        if sort_column.get_untracked() == Some(column) {
+            set_ascending.update(|a| *a = !*a);
+        } else {
+            set_sort_column.set(Some(column));
+            set_ascending.set(true);
+        }
+    };
+
+    let cells = Memo::new(move |_| {
+        table.with(|t| {
+            t.as_ref()
+                .map(|t| t.rows.iter().map(|r| r.get_cells()).collect::<Vec<_>>())
+                .unwrap_or_default()
+        })
+    });
+
+    // Index of the rows matching all column filters, sorted
+    let visible_rows = Memo::new(move |_| {
+        let filters: Vec<String> = filters.get().iter().map(|f| f.to_lowercase()).collect();
+        cells.with(|cells| {
+            let mut rows: Vec<usize> = (0..cells.len())
+                .filter(|i| {
+                    cells[*i]
+                        .iter()
+                        .zip(&filters)
+                        .all(|(cell, filter)| cell.to_lowercase().contains(filter))
+                })
+                .collect();
+            if let Some(column) = sort_column.get() {
+                rows.sort_by(|a, b| compare_cells(&cells[*a][column], &cells[*b][column]));
+                if !is_ascending.get() {
+                    rows.reverse();
+                }
+            }
+            rows
+        })
+    });
+
+    let copy_csv = move |_| {
+        let csv = table.with_untracked(|table| {
+            table.as_ref().map(|table| {
+                let rows: Vec<_> =
+                    visible_rows.with_untracked(|r| r.iter().map(|i| &table.rows[*i]).collect());
+                table.to_csv(&rows)
+            })
+        });
+        if let Some(csv) = csv {
+            copy(&csv);
+        }
+    };
+
+    let headers = move || table.with(|t| t.as_ref().map(|t| t.get_headers()).unwrap_or_default());
+
+    view! {
+        <div class="table-viewer">
+            <div class="table-viewer-toolbar">
+                <span>{move || visible_rows.with(|r| r.len())} " rows"</span>
+                <button type="button" on:click=copy_csv>
+                    {move || if copied.get() { "Copied" } else { "Copy CSV" }}
+                </button>
+            </div>
+            <table>
+                <tr>
+                    {move || {
+                        headers()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, header)| {
+                                view! {
+                                    <th on:click=move |_| sort_by(i)>
+                                        {header}
+                                        {move || match (sort_column.get(), is_ascending.get()) {
+                                            (Some(c), true) if c == i => " ▲",
+                                            (Some(c), false) if c == i => " ▼",
+                                            _ => "",
+                                        }}
+                                    </th>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </tr>
+                <tr>
+                    {move || {
+                        (0..headers().len())
+                            .map(|i| {
+                                view! {
+                                    <th>
+                                        <input
+                                            type="text"
+                                            placeholder="Filter"
+                                            on:input=move |ev| {
+                                                let value = event_target_value(&ev);
+                                                set_filters
+                                                    .update(|f| {
+                                                        if let Some(f) = f.get_mut(i) {
+                                                            *f = value;
+                                                        }
+                                                    })
+                                            }
+                                        />
+                                    </th>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </tr>
+                {move || {
+                    let rows: Vec<Vec<String>> = cells
+                        .with(|cells| visible_rows.get().iter().map(|i| cells[*i].clone()).collect());
+                    rows.into_iter()
+                        .map(|cells| {
+                            let id = cells[0].clone();
+                            view! {
+                                <tr>
+                                    {cells
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(i, cell)| {
+                                            if i == 0 {
+                                                let id = id.clone();
+                                                view! {
+                                                    <td
+                                                        class="table-viewer-id"
+                                                        title="Check relationship"
+                                                        on:click=move |_| {
+                                                            set_selected_object_id.set(id.clone());
+                                                            set_rhs_mode.set(RHSMode::Rel(id.clone()));
+                                                        }
+                                                    >
+                                                        {cell}
+                                                    </td>
+                                                }
+                                                    .into_any()
+                                            } else {
+                                                view! { <td>{cell}</td> }.into_any()
+                                            }
+                                        })
+                                        .collect_view()}
+                                </tr>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,168 @@
+use leptos::prelude::*;
+
+use crate::model::model_dict::{ValueBreakdown, ValueCount, MISSING_VALUE_KEY};
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortColumn {
+    Value,
+    Type,
+    Count,
+}
+
+#[component]
+pub fn ValueBreakdownViewer(
+    breakdown: ReadSignal<Option<ValueBreakdown>>,
+    on_filter: Callback<String>,
+) -> impl IntoView {
+    let (sort_column, set_sort_column) = signal(SortColumn::Count);
+    let (is_ascending, set_ascending) = signal(false);
+
+    // Toggle direction when sorting the same column again
+    let sort_by = move |column: SortColumn| {
+        if sort_column.get_untracked() == column {
+            set_ascending.update(|a| *a = !*a);
+        } else {
+            set_sort_column.set(column);
+            set_ascending.set(column != SortColumn::Count);
+        }
+    };
+
+    let sort_indicator = move |column: SortColumn| {
+        if sort_column.get() != column {
+            ""
+        } else if is_ascending.get() {
+            " ▲"
+        } else {
+            " ▼"
+        }
+    };
+
+    view! {
+        <div class="flex-container-rhs-model-stats">
+            <h2 class="label-model-stats">Value Breakdown</h2>
+            {move || match breakdown.get() {
+                Some(breakdown) => {
+                    let mut values = breakdown.values.clone();
+                    sort_values(&mut values, sort_column.get(), is_ascending.get());
+                    view! {
+                        <h3 class="label-model-stats">
+                            {breakdown.facet_type.clone()} " " {breakdown.pointer.clone()}
+                        </h3>
+                        <h4 class="label-model-stats">
+                            "Elements: " {breakdown.element_count} " Distinct: "
+                            {breakdown.values.len()}
+                        </h4>
+                        <h4 class="label-model-stats">By value type</h4>
+                        <div class="table-model-stats">
+                            <table>
+                                {breakdown
+                                    .by_value_type
+                                    .value
+                                    .iter()
+                                    .map(|c| {
+                                        view! {
+                                            <tr>
+                                                <td>{c.element.clone()}</td>
+                                                <td>{c.count}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                                <tr>
+                                    <td>"missing"</td>
+                                    <td>{breakdown.missing_count}</td>
+                                </tr>
+                            </table>
+                        </div>
+
+                        <h4 class="label-model-stats">By element type</h4>
+                        <div class="table-model-stats">
+                            <table>
+                                {breakdown
+                                    .by_element_type
+                                    .value
+                                    .iter()
+                                    .map(|c| {
+                                        view! {
+                                            <tr>
+                                                <td>{c.element.clone()}</td>
+                                                <td>{c.count}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </table>
+                        </div>
+
+                        <h4 class="label-model-stats">By value</h4>
+                        <div class="table-model-stats">
+                            <table class="value-breakdown-table">
+                                <tr>
+                                    <th on:click=move |_| sort_by(SortColumn::Value)>
+                                        "Value" {move || sort_indicator(SortColumn::Value)}
+                                    </th>
+                                    <th on:click=move |_| sort_by(SortColumn::Type)>
+                                        "Value type" {move || sort_indicator(SortColumn::Type)}
+                                    </th>
+                                    <th on:click=move |_| sort_by(SortColumn::Count)>
+                                        "Count" {move || sort_indicator(SortColumn::Count)}
+                                    </th>
+                                </tr>
+                                {values
+                                    .into_iter()
+                                    .map(|v| {
+                                        let key = v.key.clone();
+                                        view! {
+                                            <tr
+                                                class="value-breakdown-row"
+                                                title="Filter elements with this value"
+                                                on:click=move |_| on_filter.run(key.clone())
+                                            >
+                                                <td>{v.display}</td>
+                                                <td>{v.value_type}</td>
+                                                <td>{v.count}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                                {(breakdown.missing_count > 0)
+                                    .then(|| {
+                                        view! {
+                                            <tr
+                                                class="value-breakdown-row"
+                                                title="Filter elements without this value"
+                                                on:click=move |_| {
+                                                    on_filter.run(MISSING_VALUE_KEY.to_string())
+                                                }
+                                            >
+                                                <td>{MISSING_VALUE_KEY}</td>
+                                                <td></td>
+                                                <td>{breakdown.missing_count}</td>
+                                            </tr>
+                                        }
+                                    })}
+                            </table>
+                        </div>
+                    }
+                        .into_any()
+                }
+                None => view! { <span class="json-error">"No value breakdown"</span> }.into_any(),
+            }}
+        </div>
+    }
+}
+
+fn sort_values(values: &mut [ValueCount], column: SortColumn, is_ascending: bool) {
+    values.sort_by(|a, b| {
+        let ordering = match column {
+            SortColumn::Value => a.display.cmp(&b.display),
+            SortColumn::Type => a.value_type.cmp(&b.value_type),
+            SortColumn::Count => a.count.cmp(&b.count),
+        };
+        if is_ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+}
@@ -12,7 +12,8 @@ async fn main() -> std::io::Result<()> {
     use leptos_meta::MetaTags;
     use leptos_model_parser::app::*;
     use leptos_model_parser::model::app_state::AppState;
-    use leptos_model_parser::model::database_util::{self, connect_to_db};
+    use leptos_model_parser::model::database_util::connect_to_db;
+    use leptos_model_parser::model::saved_query_store::create_saved_query_table;
 
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
@@ -21,8 +22,14 @@ async fn main() -> std::io::Result<()> {
     dotenv().ok();
 
     // Setup DB connection
-    let pg_pool = connect_to_db().await;
-    let app_state = AppState::new().await;
+    let pg_pool = connect_to_db()
+        .await
+        .map_err(|e| std::io::Error::other(format!("Unable to connect to DB: {}", e)))?;
+    // Saved queries are optional, keep serving models without them
+    if let Err(e) = create_saved_query_table(&pg_pool).await {
+        eprintln!("[main] Unable to create saved query table: {}", e);
+    }
+    let app_state = AppState::new(pg_pool);
 
     HttpServer::new(move || {
 
@@ -39,6 +46,9 @@ async fn main() -> std::io::Result<()> {
             .service(Files::new("/assets", &site_root))
             // serve the favicon from /favicon.ico
             .service(favicon)
+            // liveness / readiness probes
+            .service(healthz)
+            .service(readyz)
             .leptos_routes(routes, {
                 let leptos_options = leptos_options.clone();
                 move || {
@@ -80,6 +90,37 @@ async fn favicon(
     ))?)
 }
 
+#[cfg(feature = "ssr")]
+#[actix_web::get("/healthz")]
+async fn healthz() -> actix_web::HttpResponse {
+    actix_web::HttpResponse::Ok().json(serde_json::json!({ "status": "ok" }))
+}
+
+#[cfg(feature = "ssr")]
+#[actix_web::get("/readyz")]
+async fn readyz(
+    app_state: actix_web::web::Data<leptos_model_parser::model::app_state::AppState>,
+) -> actix_web::HttpResponse {
+    use leptos_model_parser::model::database_util::ping_db;
+
+    let cache = app_state.get_cache_occupancy();
+    match ping_db(app_state.get_pg_pool_ref()).await {
+        Ok(_) => actix_web::HttpResponse::Ok().json(serde_json::json!({
+            "status": "ready",
+            "database": "ok",
+            "cache": cache,
+        })),
+        Err(e) => {
+            eprintln!("[readyz] Database ping failed: {}", e);
+            actix_web::HttpResponse::ServiceUnavailable().json(serde_json::json!({
+                "status": "unavailable",
+                "database": e.to_string(),
+                "cache": cache,
+            }))
+        }
+    }
+}
+
 #[cfg(not(any(feature = "ssr", feature = "csr")))]
 pub fn main() {
     // no client-side main function
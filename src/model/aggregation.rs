@@ -0,0 +1,343 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::model::cubs_model::{Element, FacetType, ModelData};
+use crate::model::element_table::{split_list, TableColumn};
+use crate::model::model_dict::{
+    get_element_value_key, get_value_display, get_value_key, MISSING_VALUE_KEY,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Mean,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateSpec {
+    pub function: AggregateFunction,
+    // Count without column counts the elements of the group
+    pub column: Option<TableColumn>,
+}
+
+// Element filter of a query, with its value filter at the pointer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementFilter {
+    pub id: String,
+    pub types: String,
+    pub natures: String,
+    pub facet_type: String,
+    pub pointer: String,
+    pub value_filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AggregationResult {
+    pub group_by: Vec<TableColumn>,
+    pub aggregates: Vec<AggregateSpec>,
+    pub element_count: usize,
+    pub groups: Vec<GroupResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupResult {
+    // Value keys of the group, used to drill down into its elements
+    pub keys: Vec<String>,
+    pub labels: Vec<String>,
+    pub element_count: usize,
+    // One value per aggregate, None when the group has no numeric value
+    pub values: Vec<Option<f64>>,
+}
+
+#[derive(Default)]
+struct Accumulator {
+    count: usize,
+    sum: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl AggregateFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "count" => Some(AggregateFunction::Count),
+            "sum" => Some(AggregateFunction::Sum),
+            "min" => Some(AggregateFunction::Min),
+            "max" => Some(AggregateFunction::Max),
+            "mean" => Some(AggregateFunction::Mean),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AggregateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregateFunction::Count => write!(f, "count"),
+            AggregateFunction::Sum => write!(f, "sum"),
+            AggregateFunction::Min => write!(f, "min"),
+            AggregateFunction::Max => write!(f, "max"),
+            AggregateFunction::Mean => write!(f, "mean"),
+        }
+    }
+}
+
+impl AggregateSpec {
+    // Aggregates from "count" or "function(facetType:/pointer)" separated by commas
+    pub fn parse_list(value: &str) -> Result<Vec<AggregateSpec>, String> {
+        split_list(value)
+            .iter()
+            .map(|a| {
+                let a = a.trim_end();
+                let (name, column) = match a.split_once('(') {
+                    Some((name, column)) => {
+                        let column = column
+                            .strip_suffix(')')
+                            .ok_or(format!("Invalid aggregate {}", a))?;
+                        (name, Some(TableColumn::parse(column)?))
+                    }
+                    None => (a, None),
+                };
+                let function = AggregateFunction::from_name(name.trim())
+                    .ok_or(format!("Invalid aggregate function {}", name))?;
+                let spec = AggregateSpec { function, column };
+                spec.validate()?;
+                Ok(spec)
+            })
+            .collect()
+    }
+
+    // Aggregates sent as a json array
+    pub fn from_json(value: &str) -> Result<Vec<AggregateSpec>, String> {
+        let specs: Vec<AggregateSpec> =
+            serde_json::from_str(value).map_err(|e| format!("Invalid aggregates {}", e))?;
+        specs.iter().try_for_each(AggregateSpec::validate)?;
+        Ok(specs)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.column {
+            Some(column) => column.validate(),
+            None if self.function == AggregateFunction::Count => Ok(()),
+            None => Err(format!("Aggregate {} requires a column", self.function)),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{}({})", self.function, column.form_value()),
+            None => self.function.to_string(),
+        }
+    }
+}
+
+impl ElementFilter {
+    // Same elements as the query result list
+    pub fn get_elements<'a>(&self, model: &'a ModelData) -> Vec<&'a Element> {
+        let mut elements = model.get_filtered_elements(&self.id, &self.types, &self.natures);
+        if let (Some(value_filter), Some(facet_type)) = (
+            &self.value_filter,
+            FacetType::from_form_value(&self.facet_type),
+        ) {
+            elements
+                .retain(|e| get_element_value_key(e, &facet_type, &self.pointer) == *value_filter);
+        }
+        elements
+    }
+}
+
+impl Accumulator {
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |m| m.min(value)));
+        self.max = Some(self.max.map_or(value, |m| m.max(value)));
+    }
+
+    fn get_value(&self, function: AggregateFunction) -> Option<f64> {
+        match function {
+            AggregateFunction::Count => Some(self.count as f64),
+            AggregateFunction::Sum => (self.count > 0).then_some(self.sum),
+            AggregateFunction::Min => self.min,
+            AggregateFunction::Max => self.max,
+            AggregateFunction::Mean => (self.count > 0).then(|| self.sum / self.count as f64),
+        }
+    }
+}
+
+// Value keys of an element for each group by column
+pub fn get_group_keys(element: &Element, group_by: &[TableColumn]) -> Vec<String> {
+    group_by
+        .iter()
+        .map(|column| match get_column_value(element, column) {
+            Some(value) => get_value_key(&value),
+            None => MISSING_VALUE_KEY.to_string(),
+        })
+        .collect()
+}
+
+// Group elements by the values of the group by columns and aggregate each group
+pub fn aggregate_elements(
+    elements: &[&Element],
+    group_by: Vec<TableColumn>,
+    aggregates: Vec<AggregateSpec>,
+) -> AggregationResult {
+    let mut groups: HashMap<Vec<String>, (Vec<String>, usize, Vec<Accumulator>)> = HashMap::new();
+
+    for element in elements {
+        let values: Vec<Option<Value>> = group_by
+            .iter()
+            .map(|column| get_column_value(element, column))
+            .collect();
+        let keys: Vec<String> = values
+            .iter()
+            .map(|v| {
+                v.as_ref()
+                    .map_or(MISSING_VALUE_KEY.to_string(), get_value_key)
+            })
+            .collect();
+
+        let (_, element_count, accumulators) = groups.entry(keys).or_insert_with_key(|keys| {
+            let labels = values
+                .iter()
+                .zip(keys)
+                .map(|(v, key)| {
+                    v.as_ref()
+                        .map_or(key.clone(), |v| get_value_display(v, key))
+                })
+                .collect();
+            (
+                labels,
+                0,
+                aggregates.iter().map(|_| Accumulator::default()).collect(),
+            )
+        });
+        *element_count += 1;
+
+        for (spec, accumulator) in aggregates.iter().zip(accumulators.iter_mut()) {
+            match (&spec.column, spec.function) {
+                (None, _) => accumulator.add(1.0),
+                (Some(column), AggregateFunction::Count) => {
+                    if get_column_value(element, column).is_some() {
+                        accumulator.add(1.0);
+                    }
+                }
+                (Some(column), _) => {
+                    if let Some(n) = get_column_value(element, column).and_then(|v| v.as_f64()) {
+                        accumulator.add(n);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<GroupResult> = groups
+        .into_iter()
+        .map(
+            |(keys, (labels, element_count, accumulators))| GroupResult {
+                keys,
+                labels,
+                element_count,
+                values: aggregates
+                    .iter()
+                    .zip(&accumulators)
+                    .map(|(spec, accumulator)| accumulator.get_value(spec.function))
+                    .collect(),
+            },
+        )
+        .collect();
+    groups.sort_by(|a, b| {
+        b.element_count
+            .cmp(&a.element_count)
+            .then_with(|| a.keys.cmp(&b.keys))
+    });
+
+    AggregationResult {
+        group_by,
+        aggregates,
+        element_count: elements.len(),
+        groups,
+    }
+}
+
+fn get_column_value(element: &Element, column: &TableColumn) -> Option<Value> {
+    let facet_type = FacetType::from_form_value(&column.facet_type)?;
+    element.get_json_value(&facet_type, &column.pointer, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::cubs_model::ModelData;
+    use serde_json::json;
+
+    fn model() -> ModelData {
+        serde_json::from_value(json!({
+            "schemaVersion": "1",
+            "modelId": "m1",
+            "siteModelId": "s1",
+            "version": 1,
+            "elements": [
+                {"id": "r1", "type": "Room", "nature": "Space", "version": 1,
+                 "facets": {"floor": 1, "area": 10.0}},
+                {"id": "r2", "type": "Room", "nature": "Space", "version": 1,
+                 "facets": {"floor": 1, "area": 30.0}},
+                {"id": "r3", "type": "Room", "nature": "Space", "version": 1,
+                 "facets": {"floor": 2, "area": "n/a"}},
+                {"id": "r4", "type": "Room", "nature": "Space", "version": 1, "facets": {}}
+            ],
+            "relationships": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_by_floor() {
+        let model = model();
+        let group_by = TableColumn::parse_list("facets:/floor").unwrap();
+        let aggregates = AggregateSpec::parse_list(
+            "count, sum(facets:/area), max(facets:/area), count(facets:/area)",
+        )
+        .unwrap();
+        let result = aggregate_elements(&model.get_elements(), group_by, aggregates);
+
+        assert_eq!(result.groups.len(), 3);
+        let floor_1 = &result.groups[0];
+        assert_eq!(floor_1.keys, vec!["1"]);
+        assert_eq!(
+            floor_1.values,
+            vec![Some(2.0), Some(40.0), Some(30.0), Some(2.0)]
+        );
+
+        let floor_2 = result.groups.iter().find(|g| g.keys == vec!["2"]).unwrap();
+        assert_eq!(floor_2.values, vec![Some(1.0), None, None, Some(1.0)]);
+        assert!(result
+            .groups
+            .iter()
+            .any(|g| g.keys == vec![MISSING_VALUE_KEY]));
+
+        let filter = ElementFilter {
+            id: String::new(),
+            types: "Room".to_string(),
+            natures: "All".to_string(),
+            facet_type: "facets".to_string(),
+            pointer: "/floor".to_string(),
+            value_filter: Some("1".to_string()),
+        };
+        assert_eq!(filter.get_elements(&model).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_aggregates() {
+        let specs = AggregateSpec::parse_list("mean(facets:/a)").unwrap();
+        assert_eq!(specs[0].function, AggregateFunction::Mean);
+        assert_eq!(specs[0].label(), "mean(facets:/a)");
+        assert!(AggregateSpec::parse_list("sum").is_err());
+        assert!(AggregateSpec::parse_list("median(facets:/a)").is_err());
+    }
+}
@@ -1,43 +1,83 @@
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::model::cache_info::CacheEntryInfo;
+use crate::model::containment::ContainmentForest;
 use crate::model::cubs_model::ModelData;
-use crate::model::database_util::{self, connect_to_db};
+use crate::model::disk_cache::DiskCache;
 use crate::model::element_graph::ElementGraph;
-use quick_cache::sync::Cache;
+use crate::model::model_error::ModelError;
+use crate::model::search_index::SearchIndex;
+use actix_web::web::Data;
+use leptos::prelude::ServerFnErrorErr;
+use quick_cache::{sync::Cache, Weighter};
+use serde::Serialize;
 const CACHE_SIZE: usize = 2;
+const CACHE_MEMORY_MB: u64 = 1024;
+const GRAPH_CACHE_MEMORY_MB: u64 = 256;
+const SEARCH_CACHE_MEMORY_MB: u64 = 256;
+const CONTAINMENT_CACHE_MEMORY_MB: u64 = 64;
+const VARIANT_SEPARATOR: char = ':';
 
 #[derive(Clone, Debug)]
 pub struct AppState {
     pg_pool: sqlx::Pool<sqlx::Postgres>,
     model_cache: QuickCache<ModelData>,
     graph_cache: QuickCache<ElementGraph>,
+    search_cache: QuickCache<SearchIndex>,
+    containment_cache: QuickCache<ContainmentForest>,
+    disk_cache: Option<DiskCache>,
 }
 
 impl AppState {
-    pub async fn new() -> Self {
-        // DB pool
-        let pg_pool = connect_to_db().await;
+    pub fn new(pg_pool: sqlx::Pool<sqlx::Postgres>) -> Self {
+        // Cache config
+        let estimated_items = env_or("CACHE_SIZE", CACHE_SIZE);
+        let ttl = std::env::var("CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs);
 
         // Model Cache
-        let model_cache: Arc<Cache<String, ModelData>> = Arc::new(Cache::new({
-            std::env::var("CACHE_SIZE")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(CACHE_SIZE)
-        }));
+        let model_cache = QuickCache::new(
+            "model",
+            estimated_items,
+            env_or("CACHE_MEMORY_MB", CACHE_MEMORY_MB) * 1024 * 1024,
+            ttl,
+        );
 
         // Graph Cache
-        let graph_cache: Arc<Cache<String, ElementGraph>> = Arc::new(Cache::new({
-            std::env::var("CACHE_SIZE")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(CACHE_SIZE)
-        }));
+        let graph_cache = QuickCache::new(
+            "graph",
+            estimated_items,
+            env_or("GRAPH_CACHE_MEMORY_MB", GRAPH_CACHE_MEMORY_MB) * 1024 * 1024,
+            ttl,
+        );
+
+        // Search index Cache
+        let search_cache = QuickCache::new(
+            "search",
+            estimated_items,
+            env_or("SEARCH_CACHE_MEMORY_MB", SEARCH_CACHE_MEMORY_MB) * 1024 * 1024,
+            ttl,
+        );
+
+        // Containment forest Cache, one entry per model version and containment config
+        let containment_cache = QuickCache::new(
+            "containment",
+            estimated_items,
+            env_or("CONTAINMENT_CACHE_MEMORY_MB", CONTAINMENT_CACHE_MEMORY_MB) * 1024 * 1024,
+            ttl,
+        );
 
         AppState {
             pg_pool,
-            model_cache: QuickCache { data: model_cache },
-            graph_cache: QuickCache { data: graph_cache },
+            model_cache,
+            graph_cache,
+            search_cache,
+            containment_cache,
+            disk_cache: DiskCache::from_env(),
         }
     }
 
@@ -52,31 +92,391 @@ impl AppState {
     pub fn get_graph_cache(&self) -> QuickCache<ElementGraph> {
         self.graph_cache.clone()
     }
+
+    pub fn get_search_cache(&self) -> QuickCache<SearchIndex> {
+        self.search_cache.clone()
+    }
+
+    pub fn get_containment_cache(&self) -> QuickCache<ContainmentForest> {
+        self.containment_cache.clone()
+    }
+
+    pub fn get_disk_cache(&self) -> Option<&DiskCache> {
+        self.disk_cache.as_ref()
+    }
+
+    pub fn get_cache_occupancy(&self) -> CacheOccupancyReport {
+        CacheOccupancyReport {
+            model_cache: self.model_cache.get_occupancy(),
+            graph_cache: self.graph_cache.get_occupancy(),
+            search_cache: self.search_cache.get_occupancy(),
+            containment_cache: self.containment_cache.get_occupancy(),
+        }
+    }
+
+    pub fn list_cache_entries(&self) -> Vec<CacheEntryInfo> {
+        let mut entries = self.model_cache.list_entries();
+        entries.extend(self.graph_cache.list_entries());
+        entries.extend(self.search_cache.list_entries());
+        entries.extend(self.containment_cache.list_entries());
+        entries
+    }
+
+    // Evict a model and the graph, search index and containment forests built from it
+    pub fn evict_cache_entry(&self, model_id: &str, version: &str) -> bool {
+        let model_evicted = self.model_cache.remove(model_id, version);
+        let graph_evicted = self.graph_cache.remove(model_id, version);
+        let search_evicted = self.search_cache.remove(model_id, version);
+        let containment_evicted = self.containment_cache.remove(model_id, version);
+        model_evicted || graph_evicted || search_evicted || containment_evicted
+    }
+
+    pub fn flush_cache(&self) {
+        self.model_cache.clear();
+        self.graph_cache.clear();
+        self.search_cache.clear();
+        self.containment_cache.clear();
+    }
+}
+
+// App state of the current server fn request
+pub async fn extract_app_state() -> Result<Data<AppState>, ModelError> {
+    leptos_actix::extract()
+        .await
+        .map_err(|e| ModelError::ServerFnError(ServerFnErrorErr::ServerError(e.to_string())))
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheOccupancyReport {
+    pub model_cache: CacheOccupancy,
+    pub graph_cache: CacheOccupancy,
+    pub search_cache: CacheOccupancy,
+    pub containment_cache: CacheOccupancy,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheOccupancy {
+    pub entries: usize,
+    pub weight_bytes: u64,
+    pub capacity_bytes: u64,
+}
+
+// Size estimation of cached value
+pub trait ApproxSize {
+    fn approx_size(&self) -> usize;
+}
+
+impl ApproxSize for ModelData {
+    fn approx_size(&self) -> usize {
+        ModelData::approx_size(self)
+    }
+}
+
+impl ApproxSize for ElementGraph {
+    fn approx_size(&self) -> usize {
+        ElementGraph::approx_size(self)
+    }
+}
+
+impl ApproxSize for SearchIndex {
+    fn approx_size(&self) -> usize {
+        SearchIndex::approx_size(self)
+    }
+}
+
+impl ApproxSize for ContainmentForest {
+    fn approx_size(&self) -> usize {
+        ContainmentForest::approx_size(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct CacheEntry<T> {
+    value: Arc<T>,
+    weight: u64,
+    inserted_at: Instant,
+}
+
+impl<T> Clone for CacheEntry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            weight: self.weight,
+            inserted_at: self.inserted_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CacheEntryWeighter;
+
+impl<T> Weighter<String, CacheEntry<T>> for CacheEntryWeighter {
+    fn weight(&self, _key: &String, entry: &CacheEntry<T>) -> u64 {
+        entry.weight
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct QuickCache<T> {
-    pub data: Arc<Cache<String, T>>,
+    name: &'static str,
+    ttl: Option<Duration>,
+    pub data: Arc<Cache<String, CacheEntry<T>, CacheEntryWeighter>>,
 }
 
-impl<T> QuickCache<T> {
-    pub fn get(&self, key: &str, version: &str) -> Option<T>
-    where
-        T: Clone,
-    {
-        let key = format!("{}-{}", key, version);
-        self.data.get(&key)
+impl<T: ApproxSize> QuickCache<T> {
+    pub fn new(
+        name: &'static str,
+        estimated_items: usize,
+        memory_budget_bytes: u64,
+        ttl: Option<Duration>,
+    ) -> Self {
+        QuickCache {
+            name,
+            ttl,
+            data: Arc::new(Cache::with_weighter(
+                estimated_items.max(1),
+                memory_budget_bytes.max(1),
+                CacheEntryWeighter,
+            )),
+        }
+    }
+
+    pub fn get(&self, key: &str, version: &str) -> Option<Arc<T>> {
+        let key = cache_key(key, version);
+        let entry = self.data.get(&key)?;
+
+        // Expire entry
+        if self.is_expired(&entry) {
+            println!("[QuickCache - {}] {} expired", self.name, key);
+            self.data.remove(&key);
+            return None;
+        }
+
+        Some(entry.value)
+    }
+
+    // Single flight load: concurrent callers for the same key wait for the first caller's loader
+    pub async fn get_or_load<E>(
+        &self,
+        key: &str,
+        version: &str,
+        loader: impl Future<Output = Result<Arc<T>, E>>,
+    ) -> Result<Arc<T>, E> {
+        let cache_key = cache_key(key, version);
+
+        // Drop expired entry so that it is reloaded
+        self.data
+            .remove_if(&cache_key, |entry| self.is_expired(entry));
+
+        match self.data.get_value_or_guard_async(&cache_key).await {
+            Ok(entry) => Ok(entry.value),
+            Err(guard) => {
+                println!("[QuickCache - {}] loading {}", self.name, cache_key);
+                let value = loader.await?;
+                let _ = guard.insert(self.new_entry(value.clone()));
+                Ok(value)
+            }
+        }
+    }
+
+    pub fn insert(&self, key: &str, version: &str, value: Arc<T>) {
+        let key = cache_key(key, version);
+        self.data.insert(key, self.new_entry(value));
     }
 
-    pub fn insert(&self, key: &str, version: &str, value: &T)
-    where
-        T: Clone,
-    {
+    fn new_entry(&self, value: Arc<T>) -> CacheEntry<T> {
+        let weight = value.approx_size().max(1) as u64;
         println!(
-            "[QuickCache] insert into cache with capacity: {}",
+            "[QuickCache - {}] insert {} bytes into cache with {} / {} bytes used",
+            self.name,
+            weight,
+            self.data.weight(),
             self.data.capacity()
         );
-         let key = format!("{}-{}", key, version);
-        self.data.insert(key.to_string(), value.clone());
+        if weight > self.data.capacity() {
+            println!(
+                "[QuickCache - {}] entry exceeds memory budget and will not be retained",
+                self.name
+            );
+        }
+        CacheEntry {
+            value,
+            weight,
+            inserted_at: Instant::now(),
+        }
+    }
+
+    // Remove the entry of the version and its variants
+    pub fn remove(&self, key: &str, version: &str) -> bool {
+        let key = cache_key(key, version);
+        let variant_prefix = format!("{}{}", key, VARIANT_SEPARATOR);
+        let variants: Vec<String> = self
+            .data
+            .iter()
+            .map(|(k, _)| k)
+            .filter(|k| k.starts_with(&variant_prefix))
+            .collect();
+        let is_removed = self.data.remove(&key).is_some();
+        variants
+            .iter()
+            .fold(is_removed, |is_removed, k| self.data.remove(k).is_some() || is_removed)
+    }
+
+    pub fn clear(&self) {
+        self.data.clear();
+    }
+
+    pub fn list_entries(&self) -> Vec<CacheEntryInfo> {
+        let mut entries: Vec<CacheEntryInfo> = self
+            .data
+            .iter()
+            .filter(|(_, entry)| !self.is_expired(entry))
+            .map(|(key, entry)| {
+                let (model_id, version) = split_cache_key(&key);
+                CacheEntryInfo {
+                    cache: self.name.to_string(),
+                    model_id,
+                    version,
+                    size_bytes: entry.weight,
+                    age_secs: entry.inserted_at.elapsed().as_secs(),
+                }
+            })
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.size_bytes));
+        entries
+    }
+
+    pub fn get_occupancy(&self) -> CacheOccupancy {
+        CacheOccupancy {
+            entries: self.data.len(),
+            weight_bytes: self.data.weight(),
+            capacity_bytes: self.data.capacity(),
+        }
+    }
+
+    fn is_expired(&self, entry: &CacheEntry<T>) -> bool {
+        self.ttl
+            .is_some_and(|ttl| entry.inserted_at.elapsed() >= ttl)
+    }
+}
+
+fn cache_key(key: &str, version: &str) -> String {
+    format!("{}-{}", key, version)
+}
+
+// Version of a value built from the model version with extra parameters
+pub fn variant_version(version: &str, variant: &str) -> String {
+    format!("{}{}{}", version, VARIANT_SEPARATOR, variant)
+}
+
+// Model id is an uuid containing '-', version is split from the last one
+fn split_cache_key(key: &str) -> (String, String) {
+    match key.rsplit_once('-') {
+        Some((model_id, version)) => (model_id.to_string(), version.to_string()),
+        None => (key.to_string(), String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug)]
+    struct Blob(usize);
+
+    impl ApproxSize for Blob {
+        fn approx_size(&self) -> usize {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_insert_and_get() {
+        let cache: QuickCache<Blob> = QuickCache::new("test", 2, 1000, None);
+        cache.insert("m1", "1", Arc::new(Blob(10)));
+        assert_eq!(cache.get("m1", "1").map(|v| v.0), Some(10));
+        assert!(cache.get("m1", "2").is_none());
+        assert_eq!(cache.get_occupancy().weight_bytes, 10);
+    }
+
+    #[test]
+    fn test_entry_over_budget_is_not_retained() {
+        let cache: QuickCache<Blob> = QuickCache::new("test", 2, 100, None);
+        cache.insert("m1", "1", Arc::new(Blob(1000)));
+        assert!(cache.get("m1", "1").is_none());
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let cache: QuickCache<Blob> = QuickCache::new("test", 2, 1000, Some(Duration::ZERO));
+        cache.insert("m1", "1", Arc::new(Blob(10)));
+        assert!(cache.get("m1", "1").is_none());
+        assert_eq!(cache.get_occupancy().entries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_load_coalesces_concurrent_loads() {
+        let cache: QuickCache<Blob> = QuickCache::new("test", 2, 1000, None);
+        let load_count = AtomicUsize::new(0);
+        let loader = || async {
+            load_count.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok::<_, ()>(Arc::new(Blob(10)))
+        };
+
+        let (a, b) = tokio::join!(
+            cache.get_or_load("m1", "1", loader()),
+            cache.get_or_load("m1", "1", loader())
+        );
+
+        assert_eq!(a.unwrap().0, 10);
+        assert_eq!(b.unwrap().0, 10);
+        assert_eq!(load_count.load(Ordering::SeqCst), 1);
+        assert!(cache.get("m1", "1").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_or_load_retries_after_failed_load() {
+        let cache: QuickCache<Blob> = QuickCache::new("test", 2, 1000, None);
+        let failed = cache
+            .get_or_load("m1", "1", async { Err::<Arc<Blob>, _>("unreachable db") })
+            .await;
+        assert!(failed.is_err());
+
+        let loaded = cache
+            .get_or_load("m1", "1", async { Ok::<_, ()>(Arc::new(Blob(5))) })
+            .await;
+        assert_eq!(loaded.unwrap().0, 5);
+    }
+
+    #[test]
+    fn test_list_and_remove_entries() {
+        let model_id = "4fd3dccd-9b87-4fde-9b50-db4f57ab10e6";
+        let cache: QuickCache<Blob> = QuickCache::new("test", 2, 1000, None);
+        cache.insert(model_id, "12", Arc::new(Blob(10)));
+
+        let entries = cache.list_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].model_id, model_id);
+        assert_eq!(entries[0].version, "12");
+        assert_eq!(entries[0].size_bytes, 10);
+
+        assert!(cache.remove(model_id, "12"));
+        assert!(!cache.remove(model_id, "12"));
+        assert!(cache.list_entries().is_empty());
+
+        // Variants are removed with their version
+        cache.insert(model_id, &variant_version("12", "a"), Arc::new(Blob(10)));
+        cache.insert(model_id, "120", Arc::new(Blob(10)));
+        assert!(cache.remove(model_id, "12"));
+        assert_eq!(cache.list_entries().len(), 1);
     }
 }
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+// Cached model, graph or search index, as listed by the cache admin panel
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CacheEntryInfo {
+    pub cache: String,
+    pub model_id: String,
+    pub version: String,
+    pub size_bytes: u64,
+    pub age_secs: u64,
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+// Relationship types and natures whose source contains their target, empty lists match any
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainmentConfig {
+    pub relationship_types: Vec<String>,
+    pub relationship_natures: Vec<String>,
+}
+
+// Element of the hierarchy with the size of what it contains
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainmentEntry {
+    pub id: String,
+    pub name: String,
+    pub type_: String,
+    pub child_count: usize,
+    pub subtree_count: usize,
+}
+
+// Rooted forest, every element has at most one containing parent
+#[derive(Debug, Clone, Default)]
+pub struct ContainmentForest {
+    parent: HashMap<String, String>,
+    children: HashMap<String, Vec<String>>,
+    roots: Vec<String>,
+    subtree_counts: HashMap<String, usize>,
+}
+
+impl ContainmentConfig {
+    // Comma separated form values
+    pub fn from_form(types: &str, natures: &str) -> Self {
+        let split = |value: &str| {
+            value
+                .split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        };
+        ContainmentConfig {
+            relationship_types: split(types),
+            relationship_natures: split(natures),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self.relationship_types.is_empty() && self.relationship_natures.is_empty() {
+            true => Err("Set at least one containment relationship type or nature".to_string()),
+            false => Ok(()),
+        }
+    }
+
+    // Same key for configs listing the same types and natures in any order
+    pub fn get_key(&self) -> String {
+        let sorted = |values: &Vec<String>| {
+            let mut values = values.clone();
+            values.sort();
+            values.dedup();
+            values
+        };
+        let mut hasher = DefaultHasher::new();
+        (
+            sorted(&self.relationship_types),
+            sorted(&self.relationship_natures),
+        )
+            .hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    pub fn is_containment(&self, type_: &str, nature: &str) -> bool {
+        let is_match = |values: &Vec<String>, value: &str| {
+            values.is_empty() || values.iter().any(|v| v == value)
+        };
+        is_match(&self.relationship_types, type_) && is_match(&self.relationship_natures, nature)
+    }
+}
+
+impl ContainmentForest {
+    // Edges as container id and contained id. The first container of an element wins and edges
+    // closing a cycle are dropped, which keeps a forest
+    pub fn from(edges: &[(&str, &str)]) -> Self {
+        let mut forest = ContainmentForest::default();
+        for (parent, child) in edges {
+            if parent == child
+                || forest.parent.contains_key(*child)
+                || forest.is_ancestor(child, parent)
+            {
+                continue;
+            }
+            forest.parent.insert(child.to_string(), parent.to_string());
+            forest
+                .children
+                .entry(parent.to_string())
+                .or_default()
+                .push(child.to_string());
+        }
+
+        forest.roots = forest
+            .children
+            .keys()
+            .filter(|id| !forest.parent.contains_key(*id))
+            .cloned()
+            .collect();
+        forest.roots.sort();
+        for children in forest.children.values_mut() {
+            children.sort();
+        }
+
+        // Post order from every root, children counted before their parent
+        let mut stack: Vec<(String, bool)> =
+            forest.roots.iter().map(|r| (r.clone(), false)).collect();
+        while let Some((id, is_visited)) = stack.pop() {
+            let children = forest.children.get(&id).cloned().unwrap_or_default();
+            match is_visited {
+                true => {
+                    let count = children
+                        .iter()
+                        .map(|c| 1 + forest.subtree_counts.get(c).copied().unwrap_or(0))
+                        .sum();
+                    forest.subtree_counts.insert(id, count);
+                }
+                false => {
+                    stack.push((id, true));
+                    stack.extend(children.into_iter().map(|c| (c, false)));
+                }
+            }
+        }
+
+        forest
+    }
+
+    fn is_ancestor(&self, ancestor: &str, id: &str) -> bool {
+        let mut current = id;
+        while let Some(parent) = self.parent.get(current) {
+            if parent == ancestor {
+                return true;
+            }
+            current = parent;
+        }
+        false
+    }
+
+    pub fn get_roots(&self) -> &[String] {
+        &self.roots
+    }
+
+    pub fn get_children(&self, id: &str) -> &[String] {
+        self.children.get(id).map_or(&[], |c| c.as_slice())
+    }
+
+    // Elements contained directly or not
+    pub fn get_subtree_count(&self, id: &str) -> usize {
+        self.subtree_counts.get(id).copied().unwrap_or(0)
+    }
+
+    // Containers from the root down to the element itself
+    pub fn get_breadcrumbs(&self, id: &str) -> Vec<String> {
+        let mut breadcrumbs = vec![id.to_string()];
+        let mut current = id;
+        while let Some(parent) = self.parent.get(current) {
+            breadcrumbs.push(parent.clone());
+            current = parent;
+        }
+        breadcrumbs.reverse();
+        breadcrumbs
+    }
+
+    // Elements in the hierarchy, as container or contained
+    pub fn get_element_count(&self) -> usize {
+        self.parent.len() + self.roots.len()
+    }
+
+    // Approximate heap size in bytes, used to weigh cached forests
+    pub fn approx_size(&self) -> usize {
+        let entry_size = std::mem::size_of::<String>() + 48;
+        let ids_size: usize = self
+            .parent
+            .iter()
+            .map(|(child, parent)| 2 * entry_size + child.len() + parent.len())
+            .sum();
+        let children_size: usize = self
+            .children
+            .values()
+            .map(|children| {
+                entry_size + children.iter().map(|c| entry_size + c.len()).sum::<usize>()
+            })
+            .sum();
+        ids_size + children_size + self.subtree_counts.len() * entry_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_containment_forest() {
+        let config = ContainmentConfig::from_form(" contains, hasPart ,", "");
+        assert_eq!(config.relationship_types, vec!["contains", "hasPart"]);
+        assert!(config.is_containment("contains", "Space"));
+        assert!(!config.is_containment("feeds", "Space"));
+        assert!(ContainmentConfig::default().validate().is_err());
+        assert_eq!(
+            config.get_key(),
+            ContainmentConfig::from_form("hasPart, contains", "").get_key()
+        );
+        assert_ne!(
+            config.get_key(),
+            ContainmentConfig::from_form("contains", "").get_key()
+        );
+
+        // site -> building -> {floor1, floor2} -> space, second parent and cycle dropped
+        let forest = ContainmentForest::from(&[
+            ("site", "building"),
+            ("building", "floor2"),
+            ("building", "floor1"),
+            ("floor1", "space"),
+            ("floor2", "space"),
+            ("space", "site"),
+            ("other", "room"),
+        ]);
+
+        assert_eq!(forest.get_roots(), ["other", "site"]);
+        assert_eq!(forest.get_children("building"), ["floor1", "floor2"]);
+        assert_eq!(forest.get_subtree_count("site"), 4);
+        assert_eq!(forest.get_subtree_count("floor2"), 0);
+        assert_eq!(
+            forest.get_breadcrumbs("space"),
+            vec!["site", "building", "floor1", "space"]
+        );
+        assert_eq!(forest.get_element_count(), 7);
+    }
+}
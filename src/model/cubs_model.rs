@@ -24,6 +24,12 @@ pub struct ModelData {
     #[serde(deserialize_with = "null_to_empty_vec")]
     pub relationships: Vec<Relationship>,
     // pub relationships: Value,
+    // Element id to position in elements, built once after loading
+    #[serde(skip)]
+    pub(crate) element_index: HashMap<String, usize>,
+    // Relationship id to position in relationships, built with the element index
+    #[serde(skip)]
+    pub(crate) relationship_index: HashMap<String, usize>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -77,13 +83,40 @@ pub struct Relationship {
     pub core_facets: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug)]
+// Parents are on the in side of the relationships of an element, children on the out side
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum RelationshipDirection {
+    Parent,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum FacetType {
     CoreFacets,
     DynamicFacets,
     Facets,
 }
 
+impl FacetType {
+    // Facet type from the value of the facet type selector
+    pub fn from_form_value(value: &str) -> Option<FacetType> {
+        match value {
+            "dynamicFacets" => Some(FacetType::DynamicFacets),
+            "coreFacets" => Some(FacetType::CoreFacets),
+            "facets" => Some(FacetType::Facets),
+            _ => None,
+        }
+    }
+
+    pub fn form_value(&self) -> &'static str {
+        match self {
+            FacetType::CoreFacets => "coreFacets",
+            FacetType::DynamicFacets => "dynamicFacets",
+            FacetType::Facets => "facets",
+        }
+    }
+}
+
 impl fmt::Display for FacetType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -278,12 +311,56 @@ impl ModelData {
         self.elements.iter().collect()
     }
 
+    pub fn build_element_index(&mut self) {
+        self.element_index = self
+            .elements
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.id.clone(), i))
+            .collect();
+        self.build_relationship_index();
+    }
+
+    pub fn build_relationship_index(&mut self) {
+        self.relationship_index = self
+            .relationships
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.id.clone(), i))
+            .collect();
+    }
+
+    pub fn get_relationship_with_id(&self, id: &str) -> Option<&Relationship> {
+        match self.relationship_index.get(id) {
+            Some(i) => self.relationships.get(*i),
+            None => self.relationships.iter().find(|r| r.id == id),
+        }
+    }
+
     pub fn get_element_with_id(&self, id: &str) -> Option<&Element> {
+        if !self.element_index.is_empty() {
+            return self
+                .element_index
+                .get(id)
+                .and_then(|i| self.elements.get(*i));
+        }
+
         let id_filter = |e: &Element| e.id == id;
         let r: Vec<&Element> = self.get_element_with_filter(id_filter);
         r.first().copied()
     }
 
+    // Elements of the element filtering inputs. "All" matches any type or nature
+    pub fn get_filtered_elements(&self, id: &str, types: &str, natures: &str) -> Vec<&Element> {
+        let mut elements = match id.is_empty() {
+            true => self.get_elements(),
+            false => self.get_element_with_id(id).into_iter().collect(),
+        };
+        elements.retain(|e| natures == "All" || e.nature == natures);
+        elements.retain(|e| types == "All" || e.type_ == types);
+        elements
+    }
+
     pub fn get_element_with_filter<F>(&self, filter: F) -> Vec<&Element>
     where
         F: Fn(&Element) -> bool,
@@ -313,6 +390,75 @@ impl ModelData {
     }
 }
 
+impl ModelData {
+    // Approximate heap size in bytes, used to weigh cached models
+    pub fn approx_size(&self) -> usize {
+        let elements_size: usize = self
+            .elements
+            .iter()
+            .map(|e| {
+                std::mem::size_of::<Element>()
+                    + e.id.len()
+                    + e.type_.len()
+                    + e.nature.len()
+                    + e.name.len()
+                    + approx_facets_size(&e.dynamic_facets)
+                    + approx_facets_size(&e.facets)
+                    + approx_facets_size(&e.core_facets)
+            })
+            .sum();
+        let relationships_size: usize = self
+            .relationships
+            .iter()
+            .map(|r| {
+                std::mem::size_of::<Relationship>()
+                    + r.id.len()
+                    + r.source_id.len()
+                    + r.target_id.len()
+                    + r.type_.len()
+                    + r.nature.len()
+                    + r.name.len()
+                    + approx_facets_size(&r.dynamic_facets)
+                    + approx_facets_size(&r.facets)
+                    + approx_facets_size(&r.core_facets)
+            })
+            .sum();
+
+        std::mem::size_of::<ModelData>()
+            + self.schema_version.len()
+            + self.model_id.len()
+            + self.site_model_id.len()
+            + elements_size
+            + relationships_size
+            + self
+                .element_index
+                .keys()
+                .chain(self.relationship_index.keys())
+                .map(|k| std::mem::size_of::<(String, usize)>() + k.len())
+                .sum::<usize>()
+    }
+}
+
+fn approx_facets_size(facets: &HashMap<String, Value>) -> usize {
+    facets
+        .iter()
+        .map(|(k, v)| std::mem::size_of::<String>() + k.len() + approx_value_size(v))
+        .sum()
+}
+
+pub fn approx_value_size(value: &Value) -> usize {
+    let content_size = match value {
+        Value::String(s) => s.len(),
+        Value::Array(arr) => arr.iter().map(approx_value_size).sum(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| std::mem::size_of::<String>() + k.len() + approx_value_size(v))
+            .sum(),
+        _ => 0,
+    };
+    std::mem::size_of::<Value>() + content_size
+}
+
 pub fn truncate_value(values: &[Value], truncate_depth: usize) -> Vec<Value> {
     let result = values
         .iter()
@@ -375,4 +521,34 @@ mod tests {
         let result_string = result.to_string();
         assert_eq!(result_string, r#"{"a":1,"b":{"c":null}}"#);
     }
+
+    #[test]
+    fn test_get_element_with_id_uses_index() {
+        let mut model_data: ModelData = serde_json::from_value(json!({
+            "schemaVersion": "1",
+            "modelId": "m1",
+            "siteModelId": "s1",
+            "version": 1,
+            "elements": [
+                {"id": "e1", "type": "Pump", "nature": "Asset", "version": 1, "facets": {}},
+                {"id": "e2", "type": "Pipe", "nature": "Asset", "version": 1, "facets": {}}
+            ],
+            "relationships": null
+        }))
+        .unwrap();
+
+        assert_eq!(model_data.get_element_with_id("e2").map(|e| e.type_.as_str()), Some("Pipe"));
+
+        model_data.build_element_index();
+        assert_eq!(model_data.get_element_with_id("e2").map(|e| e.type_.as_str()), Some("Pipe"));
+        assert!(model_data.get_element_with_id("e3").is_none());
+    }
+
+    #[test]
+    fn test_approx_value_size_grows_with_content() {
+        let small = json!({"a": 1});
+        let large = json!({"a": 1, "b": "a much longer string value", "c": [1, 2, 3]});
+        assert!(approx_value_size(&large) > approx_value_size(&small));
+        assert_eq!(approx_value_size(&Value::Null), std::mem::size_of::<Value>());
+    }
 }
@@ -1,20 +1,82 @@
-
 use sqlx::postgres::PgPoolOptions;
 use std::{env, time::Duration};
 
+const DB_CONNECT_MAX_ATTEMPTS: u32 = 10;
+const DB_CONNECT_INITIAL_BACKOFF_MS: u64 = 500;
+const DB_CONNECT_MAX_BACKOFF_MS: u64 = 30_000;
+const DB_PING_TIMEOUT_SECS: u64 = 2;
+
 /* DB */
-pub async fn connect_to_db() -> sqlx::Pool<sqlx::Postgres> {
-
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let pg_pool = PgPoolOptions::new()
-        .max_connections(5)
-        .acquire_timeout(Duration::from_secs(10))//Max time to wait for a connection
-        .idle_timeout(Duration::from_secs(60)) //Max idle time for connection in a pool
-        .max_lifetime(Duration::from_secs(3600)) // Max lifetime for a connection
-        .connect(&database_url)
-        .await
-        .expect("Failed to create DB pool.");
-
-    println!("Connected to the database");
-    pg_pool
+// Connect to the DB, retrying with exponential backoff until DB_CONNECT_MAX_ATTEMPTS is reached
+pub async fn connect_to_db() -> Result<sqlx::Pool<sqlx::Postgres>, sqlx::Error> {
+    let database_url = env::var("DATABASE_URL")
+        .map_err(|_| sqlx::Error::Configuration("DATABASE_URL must be set".into()))?;
+    let max_attempts = env::var("DB_CONNECT_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DB_CONNECT_MAX_ATTEMPTS)
+        .max(1);
+
+    let mut attempt = 1;
+    loop {
+        let result = PgPoolOptions::new()
+            .max_connections(5)
+            .acquire_timeout(Duration::from_secs(10)) //Max time to wait for a connection
+            .idle_timeout(Duration::from_secs(60)) //Max idle time for connection in a pool
+            .max_lifetime(Duration::from_secs(3600)) // Max lifetime for a connection
+            .connect(&database_url)
+            .await;
+
+        match result {
+            Ok(pg_pool) => {
+                println!("Connected to the database");
+                return Ok(pg_pool);
+            }
+            Err(e) if attempt < max_attempts => {
+                let delay = backoff_delay(attempt);
+                eprintln!(
+                    "[connect_to_db] Attempt {}/{} failed: {}. Retrying in {:?}",
+                    attempt, max_attempts, e, delay
+                );
+                actix_web::rt::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                eprintln!(
+                    "[connect_to_db] Giving up after {} attempts: {}",
+                    attempt, e
+                );
+                return Err(e);
+            }
+        }
+    }
+}
+
+// Check that the pool can still reach the DB
+pub async fn ping_db(pg_pool: &sqlx::Pool<sqlx::Postgres>) -> Result<(), sqlx::Error> {
+    let ping = sqlx::query("SELECT 1").execute(pg_pool);
+    match actix_web::rt::time::timeout(Duration::from_secs(DB_PING_TIMEOUT_SECS), ping).await {
+        Ok(result) => result.map(|_| ()),
+        Err(_) => Err(sqlx::Error::PoolTimedOut),
+    }
+}
+
+fn backoff_delay(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    let delay_ms = DB_CONNECT_INITIAL_BACKOFF_MS.saturating_mul(1 << exponent);
+    Duration::from_millis(delay_ms.min(DB_CONNECT_MAX_BACKOFF_MS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_doubles_until_cap() {
+        assert_eq!(backoff_delay(1), Duration::from_millis(500));
+        assert_eq!(backoff_delay(2), Duration::from_millis(1000));
+        assert_eq!(backoff_delay(3), Duration::from_millis(2000));
+        assert_eq!(backoff_delay(10), Duration::from_millis(DB_CONNECT_MAX_BACKOFF_MS));
+        assert_eq!(backoff_delay(u32::MAX), Duration::from_millis(DB_CONNECT_MAX_BACKOFF_MS));
+    }
 }
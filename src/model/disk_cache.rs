@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::model::cubs_model::ModelData;
+
+// File layout: MAGIC | FORMAT_VERSION (u32) | payload length (u64) | crc32 of payload (u32) | payload
+const MAGIC: &[u8; 4] = b"MPDC";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 4 + 4 + 8 + 4;
+
+// Second tier cache of decompressed models stored on disk.
+// Saved model versions are immutable so entries are never invalidated, only integrity checked.
+#[derive(Clone, Debug)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct DiskCachedModel {
+    model_data: ModelData,
+    element_index: HashMap<String, usize>,
+}
+
+#[derive(Serialize)]
+struct DiskCachedModelRef<'a> {
+    model_data: &'a ModelData,
+    element_index: &'a HashMap<String, usize>,
+}
+
+impl DiskCache {
+    pub fn new(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(DiskCache { dir })
+    }
+
+    // Disk cache is enabled when DISK_CACHE_DIR is set
+    pub fn from_env() -> Option<Self> {
+        let dir = std::env::var("DISK_CACHE_DIR").ok()?;
+        match DiskCache::new(&dir) {
+            Ok(disk_cache) => {
+                println!("[DiskCache] Using disk cache directory {}", dir);
+                Some(disk_cache)
+            }
+            Err(e) => {
+                eprintln!(
+                    "[DiskCache] Unable to use disk cache directory {}: {}",
+                    dir, e
+                );
+                None
+            }
+        }
+    }
+
+    // File reads and decoding run on the blocking pool, not to stall the worker of the request
+    pub async fn read(&self, model_id: &str, vers_no: i32) -> Option<ModelData> {
+        let disk_cache = self.clone();
+        let model_id = model_id.to_string();
+        tokio::task::spawn_blocking(move || disk_cache.read_blocking(&model_id, vers_no))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    pub async fn write(
+        &self,
+        model_id: &str,
+        vers_no: i32,
+        model_data: Arc<ModelData>,
+    ) -> Result<(), Box<dyn Error>> {
+        let disk_cache = self.clone();
+        let model_id = model_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            disk_cache
+                .write_blocking(&model_id, vers_no, &model_data)
+                .map_err(|e| e.to_string())
+        })
+        .await?
+        .map_err(|e| anyhow!(e).into())
+    }
+
+    fn read_blocking(&self, model_id: &str, vers_no: i32) -> Option<ModelData> {
+        let start_time = Instant::now();
+        let path = self.path(model_id, vers_no);
+        let bytes = fs::read(&path).ok()?;
+
+        match decode(&bytes) {
+            Ok(model_data) => {
+                let elapsed_time = start_time.elapsed();
+                println!("[Execution time] DiskCache - read - {:?}", elapsed_time);
+                Some(model_data)
+            }
+            Err(e) => {
+                // Corrupted entry will be re-written after the next DB load
+                eprintln!(
+                    "[DiskCache] Discarding corrupted entry {}: {}",
+                    path.display(),
+                    e
+                );
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    fn write_blocking(
+        &self,
+        model_id: &str,
+        vers_no: i32,
+        model_data: &ModelData,
+    ) -> Result<(), Box<dyn Error>> {
+        let start_time = Instant::now();
+        let path = self.path(model_id, vers_no);
+        let bytes = encode(model_data)?;
+
+        // Write to a temporary file first so readers never see a partial entry
+        let tmp_path = path.with_extension(format!("tmp-{}", std::process::id()));
+        fs::write(&tmp_path, &bytes)?;
+        fs::rename(&tmp_path, &path)?;
+
+        let elapsed_time = start_time.elapsed();
+        println!(
+            "[DiskCache] Wrote {} bytes to {} in {:?}",
+            bytes.len(),
+            path.display(),
+            elapsed_time
+        );
+        Ok(())
+    }
+
+    fn path(&self, model_id: &str, vers_no: i32) -> PathBuf {
+        self.dir.join(format!("{}_{}.mpk", model_id, vers_no))
+    }
+}
+
+fn encode(model_data: &ModelData) -> Result<Vec<u8>, Box<dyn Error>> {
+    let payload = rmp_serde::to_vec_named(&DiskCachedModelRef {
+        model_data,
+        element_index: &model_data.element_index,
+    })?;
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+    bytes.extend_from_slice(&payload);
+    Ok(bytes)
+}
+
+fn decode(bytes: &[u8]) -> Result<ModelData, Box<dyn Error>> {
+    if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+        return Err(anyhow!("Invalid disk cache header").into());
+    }
+
+    let format_version = u32::from_le_bytes(bytes[4..8].try_into()?);
+    if format_version != FORMAT_VERSION {
+        return Err(anyhow!("Unsupported disk cache format version {}", format_version).into());
+    }
+
+    let payload_len = u64::from_le_bytes(bytes[8..16].try_into()?) as usize;
+    let checksum = u32::from_le_bytes(bytes[16..20].try_into()?);
+    let payload = &bytes[HEADER_LEN..];
+    if payload.len() != payload_len {
+        return Err(anyhow!(
+            "Truncated payload. Expected: {} vs found: {}",
+            payload_len,
+            payload.len()
+        )
+        .into());
+    }
+    if crc32fast::hash(payload) != checksum {
+        return Err(anyhow!("Checksum mismatch").into());
+    }
+
+    let cached: DiskCachedModel = rmp_serde::from_slice(payload)?;
+    let mut model_data = cached.model_data;
+    model_data.element_index = cached.element_index;
+    model_data.build_relationship_index();
+    Ok(model_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_model_data() -> ModelData {
+        let mut model_data: ModelData = serde_json::from_value(json!({
+            "schemaVersion": "1",
+            "modelId": "m1",
+            "siteModelId": "s1",
+            "version": 3,
+            "elements": [
+                {
+                    "id": "e1", "type": "Pump", "nature": "Asset", "name": "P-1", "version": 1,
+                    "facets": {"flow": {"value": 1.5}},
+                    "dynamicFacets": {"status": "on"},
+                    "area": 12
+                }
+            ],
+            "relationships": [
+                {"id": "r1", "sourceId": "e1", "targetId": "e1", "type": "feeds", "nature": "Flow", "version": 1, "facets": {}}
+            ]
+        }))
+        .unwrap();
+        model_data.build_element_index();
+        model_data
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let model_data = sample_model_data();
+        let decoded = decode(&encode(&model_data).unwrap()).unwrap();
+
+        assert_eq!(decoded.version, 3);
+        assert_eq!(decoded.element_index.get("e1"), Some(&0));
+        let element = decoded.get_element_with_id("e1").unwrap();
+        assert_eq!(element.facets["flow"], json!({"value": 1.5}));
+        assert_eq!(element.dynamic_facets["status"], json!("on"));
+        assert_eq!(element.core_facets["area"], json!(12));
+        assert_eq!(decoded.relationships[0].target_id, "e1");
+    }
+
+    #[tokio::test]
+    async fn test_write_read_entry() {
+        let dir = std::env::temp_dir().join(format!("disk_cache_test_{}", std::process::id()));
+        let disk_cache = DiskCache::new(&dir).unwrap();
+
+        assert!(disk_cache.read("m1", 3).await.is_none());
+        disk_cache
+            .write("m1", 3, Arc::new(sample_model_data()))
+            .await
+            .unwrap();
+        let read = disk_cache.read("m1", 3).await.unwrap();
+        assert_eq!(read.get_element_with_id("e1").unwrap().name, "P-1");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_decode_detects_corruption() {
+        let mut bytes = encode(&sample_model_data()).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        assert!(decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_decode_detects_truncation_and_bad_header() {
+        let bytes = encode(&sample_model_data()).unwrap();
+        assert!(decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode(b"not a cache entry").is_err());
+    }
+}
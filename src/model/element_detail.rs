@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::cubs_model::{Element, ModelData};
+
+// Common fields, facet groups and relationships of an element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementDetail {
+    pub id: String,
+    pub name: String,
+    pub type_: String,
+    pub nature: String,
+    pub version: u32,
+    pub core_facets: Value,
+    pub facets: Value,
+    pub dynamic_facets: Value,
+    pub incoming: Vec<RelationshipRow>,
+    pub outgoing: Vec<RelationshipRow>,
+}
+
+// Relationship with the element at its other end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationshipRow {
+    pub relationship_id: String,
+    pub relationship_type: String,
+    pub relationship_name: String,
+    pub counterpart_id: String,
+    pub counterpart_name: String,
+    pub counterpart_type: String,
+}
+
+impl ElementDetail {
+    // Incoming and outgoing as relationship id and counterpart id from the element graph
+    pub fn from(
+        element: &Element,
+        model: &ModelData,
+        incoming: &[(&str, &str)],
+        outgoing: &[(&str, &str)],
+    ) -> Self {
+        let to_rows = |paths: &[(&str, &str)]| {
+            let mut rows: Vec<RelationshipRow> = paths
+                .iter()
+                .map(|(relationship_id, counterpart_id)| {
+                    RelationshipRow::from(model, relationship_id, counterpart_id)
+                })
+                .collect();
+            rows.sort_by(|a, b| {
+                (&a.relationship_type, &a.counterpart_name)
+                    .cmp(&(&b.relationship_type, &b.counterpart_name))
+            });
+            rows
+        };
+
+        ElementDetail {
+            id: element.id.clone(),
+            name: element.name.clone(),
+            type_: element.type_.clone(),
+            nature: element.nature.clone(),
+            version: element.version,
+            core_facets: serde_json::to_value(&element.core_facets).unwrap_or_default(),
+            facets: serde_json::to_value(&element.facets).unwrap_or_default(),
+            dynamic_facets: serde_json::to_value(&element.dynamic_facets).unwrap_or_default(),
+            incoming: to_rows(incoming),
+            outgoing: to_rows(outgoing),
+        }
+    }
+}
+
+impl RelationshipRow {
+    // Missing relationships or counterparts keep their id with empty fields
+    pub fn from(model: &ModelData, relationship_id: &str, counterpart_id: &str) -> Self {
+        let relationship = model.get_relationship_with_id(relationship_id);
+        let counterpart = model.get_element_with_id(counterpart_id);
+
+        RelationshipRow {
+            relationship_id: relationship_id.to_string(),
+            relationship_type: relationship.map(|r| r.type_.clone()).unwrap_or_default(),
+            relationship_name: relationship.map(|r| r.name.clone()).unwrap_or_default(),
+            counterpart_id: counterpart_id.to_string(),
+            counterpart_name: counterpart.map(|e| e.name.clone()).unwrap_or_default(),
+            counterpart_type: counterpart.map(|e| e.type_.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_element_detail() {
+        let mut model: ModelData = serde_json::from_value(json!({
+            "schemaVersion": "1",
+            "modelId": "m1",
+            "siteModelId": "s1",
+            "version": 1,
+            "elements": [
+                {"id": "p1", "type": "Pump", "nature": "Asset", "name": "Pump", "version": 1,
+                 "facets": {"flow": 2}, "area": 4},
+                {"id": "v1", "type": "Valve", "nature": "Asset", "name": "Valve", "version": 1, "facets": {}},
+                {"id": "r1", "type": "Room", "nature": "Space", "name": "Room", "version": 1, "facets": {}}
+            ],
+            "relationships": [
+                {"id": "rel1", "sourceId": "p1", "targetId": "v1", "type": "feeds", "nature": "Flow",
+                 "version": 1, "facets": {}},
+                {"id": "rel2", "sourceId": "r1", "targetId": "p1", "type": "contains", "nature": "Space",
+                 "version": 1, "facets": {}}
+            ]
+        }))
+        .unwrap();
+        model.build_element_index();
+
+        let element = model.get_element_with_id("p1").unwrap();
+        let detail = ElementDetail::from(
+            element,
+            &model,
+            &[("rel2", "r1")],
+            &[("rel1", "v1"), ("missing", "gone")],
+        );
+
+        assert_eq!(detail.core_facets, json!({"area": 4}));
+        assert_eq!(detail.facets, json!({"flow": 2}));
+        assert_eq!(detail.incoming[0].relationship_type, "contains");
+        assert_eq!(detail.incoming[0].counterpart_name, "Room");
+        // Unknown relationships sort first with empty fields
+        assert_eq!(detail.outgoing[0].counterpart_id, "gone");
+        assert_eq!(detail.outgoing[0].relationship_type, "");
+        assert_eq!(detail.outgoing[1].counterpart_type, "Valve");
+    }
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::model::cubs_model::{Element, FacetType};
+use crate::model::facet_schema::escape_pointer_segment;
+
+// Element of a model version pinned for comparison
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PinnedElement {
+    pub model_id: String,
+    pub version: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ElementComparison {
+    pub elements: Vec<ComparedElement>,
+    pub groups: Vec<FacetGroupDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparedElement {
+    pub pinned: PinnedElement,
+    pub name: String,
+    pub type_: String,
+    // False when the element is not in its model version
+    pub is_found: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacetGroupDiff {
+    // Facet type form value
+    pub facet_type: String,
+    pub rows: Vec<DiffRow>,
+}
+
+// Leaf values at a pointer, one per compared element, None when missing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffRow {
+    pub pointer: String,
+    pub values: Vec<Option<Value>>,
+    pub is_different: bool,
+}
+
+impl ElementComparison {
+    // Compare the facet groups of elements, None for pinned elements not found
+    pub fn from(pinned: Vec<PinnedElement>, elements: &[Option<&Element>]) -> Self {
+        let compared = pinned
+            .into_iter()
+            .zip(elements)
+            .map(|(pinned, element)| ComparedElement {
+                pinned,
+                name: element.map(|e| e.name.clone()).unwrap_or_default(),
+                type_: element.map(|e| e.type_.clone()).unwrap_or_default(),
+                is_found: element.is_some(),
+            })
+            .collect();
+
+        let groups = [
+            FacetType::CoreFacets,
+            FacetType::Facets,
+            FacetType::DynamicFacets,
+        ]
+        .into_iter()
+        .map(|facet_type| FacetGroupDiff {
+            facet_type: facet_type.form_value().to_string(),
+            rows: diff_facet_group(elements, &facet_type),
+        })
+        .collect();
+
+        ElementComparison {
+            elements: compared,
+            groups,
+        }
+    }
+
+    pub fn get_difference_count(&self) -> usize {
+        self.groups
+            .iter()
+            .map(|g| g.rows.iter().filter(|r| r.is_different).count())
+            .sum()
+    }
+}
+
+// Rows of the union of leaf pointers of the facet group, sorted by pointer
+fn diff_facet_group(elements: &[Option<&Element>], facet_type: &FacetType) -> Vec<DiffRow> {
+    let leaves: Vec<HashMap<String, Value>> = elements
+        .iter()
+        .map(|element| {
+            let mut leaves = HashMap::new();
+            if let Some(element) = element {
+                let facets = match facet_type {
+                    FacetType::CoreFacets => &element.core_facets,
+                    FacetType::Facets => &element.facets,
+                    FacetType::DynamicFacets => &element.dynamic_facets,
+                };
+                for (key, value) in facets {
+                    collect_leaves(
+                        &format!("/{}", escape_pointer_segment(key)),
+                        value,
+                        &mut leaves,
+                    );
+                }
+            }
+            leaves
+        })
+        .collect();
+
+    let mut rows: BTreeMap<&String, Vec<Option<Value>>> = BTreeMap::new();
+    for pointer in leaves.iter().flat_map(|l| l.keys()) {
+        rows.entry(pointer)
+            .or_insert_with(|| leaves.iter().map(|l| l.get(pointer).cloned()).collect());
+    }
+
+    rows.into_iter()
+        .map(|(pointer, values)| DiffRow {
+            pointer: pointer.clone(),
+            is_different: values.windows(2).any(|w| w[0] != w[1]),
+            values,
+        })
+        .collect()
+}
+
+// Scalars and empty containers are leaves
+fn collect_leaves(pointer: &str, value: &Value, leaves: &mut HashMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let child_pointer = format!("{}/{}", pointer, escape_pointer_segment(key));
+                collect_leaves(&child_pointer, child, leaves);
+            }
+        }
+        Value::Array(arr) if !arr.is_empty() => {
+            for (i, child) in arr.iter().enumerate() {
+                collect_leaves(&format!("{}/{}", pointer, i), child, leaves);
+            }
+        }
+        _ => {
+            leaves.insert(pointer.to_string(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn element(value: Value) -> Element {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn pinned(id: &str) -> PinnedElement {
+        PinnedElement {
+            model_id: "m1".to_string(),
+            version: "1".to_string(),
+            id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_compare_elements() {
+        let a = element(
+            json!({"id": "a", "type": "Pump", "nature": "Asset", "version": 1,
+            "facets": {"flow": {"max": 2, "unit": "l/s"}, "tags": ["x"]}}),
+        );
+        let b = element(
+            json!({"id": "b", "type": "Pump", "nature": "Asset", "version": 1,
+            "facets": {"flow": {"max": 3, "unit": "l/s"}}}),
+        );
+        let comparison = ElementComparison::from(
+            vec![pinned("a"), pinned("b"), pinned("c")],
+            &[Some(&a), Some(&b), None],
+        );
+
+        assert!(!comparison.elements[2].is_found);
+        let facets = &comparison.groups[1];
+        assert_eq!(facets.facet_type, "facets");
+        let pointers: Vec<&str> = facets.rows.iter().map(|r| r.pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/flow/max", "/flow/unit", "/tags/0"]);
+        assert_eq!(
+            facets.rows[0].values,
+            vec![Some(json!(2)), Some(json!(3)), None]
+        );
+        assert!(facets.rows[0].is_different);
+
+        // Pointers missing from one element are differences
+        let comparison =
+            ElementComparison::from(vec![pinned("a"), pinned("b")], &[Some(&a), Some(&b)]);
+        let facets = &comparison.groups[1];
+        assert!(!facets.rows[1].is_different);
+        assert!(facets.rows[2].is_different);
+        assert_eq!(comparison.get_difference_count(), 2);
+    }
+}
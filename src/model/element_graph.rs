@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::model::cubs_model::{Element, Relationship};
+use crate::model::cubs_model::{Element, Relationship, RelationshipDirection};
 
 // Graph hold all the connection
 
@@ -78,6 +78,60 @@ impl ElementGraph {
         self.connectors.get(id)
     }
 
+    pub fn get_connection_mut(&mut self, id: &str) -> Option<&mut ElementConnector> {
+        self.connectors.get_mut(id)
+    }
+
+    // Every element reachable from id in the direction, with its distance, nearest first.
+    // Breadth first with a visited set, so cycles end the walk and the start is left out
+    pub fn get_closure<F>(&self, id: &str, direction: &RelationshipDirection, is_followed: F) -> Vec<(String, u32)>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.get_closure_within(id, direction, u32::MAX, is_followed)
+    }
+
+    // Closure limited to the elements at most max_distance away
+    pub fn get_closure_within<F>(
+        &self,
+        id: &str,
+        direction: &RelationshipDirection,
+        max_distance: u32,
+        is_followed: F,
+    ) -> Vec<(String, u32)>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut visited: HashSet<&str> = HashSet::from([id]);
+        let mut queue: VecDeque<(&str, u32)> = VecDeque::from([(id, 0)]);
+        let mut closure = Vec::new();
+
+        while let Some((current, distance)) = queue.pop_front() {
+            let Some(connector) = self.connectors.get(current) else {
+                continue;
+            };
+            if distance >= max_distance {
+                continue;
+            }
+            let paths = match direction {
+                RelationshipDirection::Parent => &connector.in_ids,
+                RelationshipDirection::Child => &connector.out_ids,
+            };
+            for path in paths {
+                if is_followed(&path.0) && visited.insert(&path.1) {
+                    closure.push((path.1.clone(), distance + 1));
+                    queue.push_back((&path.1, distance + 1));
+                }
+            }
+        }
+
+        closure
+    }
+
+    pub fn get_connectors(&self) -> Vec<&ElementConnector> {
+        self.connectors.values().collect()
+    }
+
     pub fn get_connection_count(&self) -> usize {
         self.connectors.len()
     }
@@ -93,6 +147,30 @@ impl ElementGraph {
     pub fn get_connected_relationship(&self) -> Vec<String> {
         self.connected_relationship.clone()
     }
+
+    // Approximate heap size in bytes, used to weigh cached graphs
+    pub fn approx_size(&self) -> usize {
+        let path_size = |p: &Path| std::mem::size_of::<Path>() + p.0.len() + p.1.len();
+        let connectors_size: usize = self
+            .connectors
+            .iter()
+            .map(|(id, c)| {
+                std::mem::size_of::<String>()
+                    + id.len()
+                    + std::mem::size_of::<ElementConnector>()
+                    + c.element_id.len()
+                    + c.in_ids.iter().map(path_size).sum::<usize>()
+                    + c.out_ids.iter().map(path_size).sum::<usize>()
+            })
+            .sum();
+        let relationships_size: usize = self
+            .connected_relationship
+            .iter()
+            .map(|r| std::mem::size_of::<String>() + r.len())
+            .sum();
+
+        std::mem::size_of::<ElementGraph>() + connectors_size + relationships_size
+    }
 }
 
 impl ElementConnector {
@@ -124,6 +202,10 @@ impl ElementConnector {
         self.out_ids.retain(|p| p.1 == id);
     }
 
+    pub fn push_out_id(&mut self, relationship_id: &str, id: &str) {
+        self.out_ids.push(Path(relationship_id.to_owned(), id.to_owned()));
+    }
+
     pub fn is_in_ids_empty(&self) -> bool {
         self.in_ids.is_empty()
     }
@@ -162,6 +244,7 @@ impl<'a> std::fmt::Display for ElementConnector {
 
 #[cfg(test)]
 mod tests {
+    use crate::model::cubs_model::RelationshipDirection;
     use crate::model::element_graph::{ElementConnector, ElementGraph};
 
     #[test]
@@ -246,4 +329,29 @@ mod tests {
 
         assert!(true);
     }
+
+    #[test]
+    fn test_closure() {
+        // c1 -> c2 -> c3 -> c1 cycle, c2 -> c4 through another relationship type
+        let mut graph = ElementGraph::new();
+        for id in ["c1", "c2", "c3", "c4"] {
+            graph.add_connector(id);
+        }
+        graph.connect("r1", "c1", "c2");
+        graph.connect("r2", "c2", "c3");
+        graph.connect("r3", "c3", "c1");
+        graph.connect("x1", "c2", "c4");
+
+        let downstream = graph.get_closure("c1", &RelationshipDirection::Child, |_| true);
+        assert_eq!(
+            downstream,
+            vec![("c2".to_string(), 1), ("c3".to_string(), 2), ("c4".to_string(), 2)]
+        );
+
+        let upstream = graph.get_closure("c2", &RelationshipDirection::Parent, |r| r.starts_with('r'));
+        assert_eq!(upstream, vec![("c1".to_string(), 1), ("c3".to_string(), 2)]);
+
+        let nearest = graph.get_closure_within("c1", &RelationshipDirection::Child, 1, |_| true);
+        assert_eq!(nearest, vec![("c2".to_string(), 1)]);
+    }
 }
@@ -1,12 +1,17 @@
-use std::{collections::HashMap, process::id};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    process::id,
+};
 
 use serde_json::Value;
 
 use crate::{
-    component::relationship_viewer::{OutputGraph, OutputLine, OutputToken, RelationshipDirection},
+    component::relationship_viewer::{
+        OutputGraph, OutputLine, OutputToken, PathCount, RelationshipDirection,
+    },
     model::{
         cubs_model::ModelData,
-        element_graph::{ElementConnector, ElementGraph, Path},
+        element_graph::{ElementConnector, ElementGraph},
         model_error::ModelError,
     },
 };
@@ -43,7 +48,6 @@ impl ElementGraphParser {
             full_graph,
             &mut partial_graph,
             target_connector,
-            1,
             ancestor_level_limit,
         );
 
@@ -52,7 +56,6 @@ impl ElementGraphParser {
             full_graph,
             &mut partial_graph,
             target_connector,
-            1,
             children_level_limit,
         );
 
@@ -69,105 +72,104 @@ impl ElementGraphParser {
         Ok(partial_graph)
     }
 
+    // Breadth first so that every element is added once, at its shallowest level
     fn parse_parent(
         source_graph: &ElementGraph,
         target_graph: &mut ElementGraph,
-        current_element_connnector: &ElementConnector,
-        current_level: u32,
+        target_connector: &ElementConnector,
         limit: u32,
     ) {
-        // Stopping condition
-        if current_level > limit {
-            return;
-        }
+        let mut visited: HashSet<&str> = HashSet::from([target_connector.get_element_id()]);
+        let mut queue: VecDeque<(&ElementConnector, u32)> = VecDeque::from([(target_connector, 1)]);
 
-        let current_element_id = current_element_connnector.get_element_id();
-        let ids: Vec<&Path> = current_element_connnector.get_in_id();
-        if ids.is_empty() {
-            return;
-        }
+        while let Some((current_element_connnector, current_level)) = queue.pop_front() {
+            // Stopping condition
+            if current_level > limit {
+                continue;
+            }
+
+            let current_element_id = current_element_connnector.get_element_id();
 
-        // Executing  For every id in the path add to the target graph
-        for path in ids {
-            let id = &path.1;
-            let relationship_id = &path.0;
-            if let Some(parent_connector) = source_graph.get_connection(&id) {
-                println!("[ElementGraphParser - parse_parent] Adding element {} with relationship {} to graph at level: {}", parent_connector.get_element_id(), relationship_id, current_level);
-
-                let mut cloned_parent_connector = parent_connector.clone();
-                // Truncate the path if reach limit
-                if current_level == limit {
-                    cloned_parent_connector.clear_in_id();
+            // Executing  For every id in the path add to the target graph
+            for path in current_element_connnector.get_in_id() {
+                let id = &path.1;
+                let relationship_id = &path.0;
+                target_graph.add_connected_relationship(relationship_id);
+
+                // Parent already added, only keep this branch too
+                if !visited.insert(id) {
+                    if let Some(parent_connector) = target_graph.get_connection_mut(id) {
+                        parent_connector.push_out_id(relationship_id, current_element_id);
+                    }
+                    continue;
                 }
 
-                // prune out other branch
-                cloned_parent_connector.retain_out_id(current_element_id);
+                if let Some(parent_connector) = source_graph.get_connection(id) {
+                    println!("[ElementGraphParser - parse_parent] Adding element {} with relationship {} to graph at level: {}", parent_connector.get_element_id(), relationship_id, current_level);
 
-                // Add to target graph
-                target_graph.push_connector(&id.to_owned(), cloned_parent_connector);
-                target_graph.add_connected_relationship(&relationship_id.to_owned());
+                    let mut cloned_parent_connector = parent_connector.clone();
+                    // Truncate the path if reach limit
+                    if current_level == limit {
+                        cloned_parent_connector.clear_in_id();
+                    }
 
-                // Recrusive call
-                ElementGraphParser::parse_parent(
-                    source_graph,
-                    target_graph,
-                    parent_connector,
-                    current_level + 1,
-                    limit,
-                    // &path_retrieval,
-                );
-            } else {
-                println!("[ElementGraphParser - parse_parent] Error parsing {}", id);
-                break;
+                    // prune out other branch
+                    cloned_parent_connector.retain_out_id(current_element_id);
+
+                    // Add to target graph
+                    target_graph.push_connector(id, cloned_parent_connector);
+                    queue.push_back((parent_connector, current_level + 1));
+                } else {
+                    println!("[ElementGraphParser - parse_parent] Error parsing {}", id);
+                    break;
+                }
             }
         }
     }
 
+    // Breadth first so that every element is added once, at its shallowest level
     fn parse_child(
         source_graph: &ElementGraph,
         target_graph: &mut ElementGraph,
-        current_element_connnector: &ElementConnector,
-        current_level: u32,
+        target_connector: &ElementConnector,
         limit: u32,
     ) {
-        // Stopping condition
-        if current_level > limit {
-            return;
-        }
+        let mut visited: HashSet<&str> = HashSet::from([target_connector.get_element_id()]);
+        let mut queue: VecDeque<(&ElementConnector, u32)> = VecDeque::from([(target_connector, 1)]);
 
-        let ids: Vec<&Path> = current_element_connnector.get_out_id();
-        if ids.is_empty() {
-            return;
-        }
+        while let Some((current_element_connnector, current_level)) = queue.pop_front() {
+            // Stopping condition
+            if current_level > limit {
+                continue;
+            }
 
-        // Executing  For every id in the path add to the target graph
-        for path in ids {
-            let id = &path.1;
-            let relationship_id = &path.0;
-            if let Some(parent_connector) = source_graph.get_connection(&id) {
-                println!("[ElementGraphParser - parse_child] Adding element {} with relationship {} to graph at level: {}", parent_connector.get_element_id(), relationship_id, current_level);
-
-                let mut cloned_parent_connector = parent_connector.clone();
-                // Truncate the path if reach limit
-                if current_level == limit {
-                    cloned_parent_connector.clear_out_id();
-                }
+            // Executing  For every id in the path add to the target graph
+            for path in current_element_connnector.get_out_id() {
+                let id = &path.1;
+                let relationship_id = &path.0;
+                target_graph.add_connected_relationship(relationship_id);
 
-                // Add to target graph
-                target_graph.push_connector(&id.to_owned(), cloned_parent_connector);
-                target_graph.add_connected_relationship(&relationship_id.to_owned());
+                // Child already added with all its out paths
+                if !visited.insert(id) {
+                    continue;
+                }
 
-                // Recrusive call
-                ElementGraphParser::parse_child(
-                    source_graph,
-                    target_graph,
-                    parent_connector,
-                    current_level + 1,
-                    limit,
-                );
-            } else {
-                println!("[ElementGraphParser - parse_child] Error parsing {}", id);
-                break;
+                if let Some(child_connector) = source_graph.get_connection(id) {
+                    println!("[ElementGraphParser - parse_child] Adding element {} with relationship {} to graph at level: {}", child_connector.get_element_id(), relationship_id, current_level);
+
+                    let mut cloned_child_connector = child_connector.clone();
+                    // Truncate the path if reach limit
+                    if current_level == limit {
+                        cloned_child_connector.clear_out_id();
+                    }
+
+                    // Add to target graph
+                    target_graph.push_connector(id, cloned_child_connector);
+                    queue.push_back((child_connector, current_level + 1));
+                } else {
+                    println!("[ElementGraphParser - parse_child] Error parsing {}", id);
+                    break;
+                }
             }
         }
     }
@@ -182,17 +184,22 @@ impl ElementGraphParser {
             parent_lines: Vec::new(),
             child_lines: Vec::new(),
             elements_data: HashMap::new(),
+            parent_count: PathCount::default(),
+            child_count: PathCount::default(),
         };
         let current_element = full_graph.get_connection(target_element_id);
 
-        // Collect traversed element
-        let mut traversed_element_ids: Vec<String> = Vec::new();
+        // Shallowest level of each element, per direction since the target heads both trees.
+        // An element is expanded at that level only, so a deeper path walked first does not
+        // cut its subtree
+        let child_levels = Self::get_levels(full_graph, target_element_id, 5, &RelationshipDirection::Child);
+        let parent_levels = Self::get_levels(full_graph, target_element_id, 2, &RelationshipDirection::Parent);
 
         // Child
         Self::dfs(
             full_graph,
             current_element,
-            &mut traversed_element_ids,
+            &mut child_levels.clone(),
             0,
             5,
             &RelationshipDirection::Child,
@@ -203,7 +210,7 @@ impl ElementGraphParser {
         Self::dfs(
             full_graph,
             current_element,
-            &mut traversed_element_ids,
+            &mut parent_levels.clone(),
             0,
             2,
             &RelationshipDirection::Parent,
@@ -211,9 +218,22 @@ impl ElementGraphParser {
         );
         output.parent_lines.reverse();
 
+        // Count the paths a full expansion would print
+        let mut memo = HashMap::new();
+        output.child_count = PathCount {
+            distinct: child_levels.len(),
+            total: Self::count_paths(full_graph, current_element, 0, 5, &RelationshipDirection::Child, &mut memo),
+        };
+        let mut memo = HashMap::new();
+        output.parent_count = PathCount {
+            distinct: parent_levels.len(),
+            total: Self::count_paths(full_graph, current_element, 0, 2, &RelationshipDirection::Parent, &mut memo),
+        };
+
         // Retrieve elements
-        let element_map = traversed_element_ids
-            .iter()
+        let element_map = child_levels
+            .keys()
+            .chain(parent_levels.keys())
             .filter_map(|id| {
                 model_data
                     .get_element_with_id(id)
@@ -229,7 +249,7 @@ impl ElementGraphParser {
     fn dfs(
         full_graph: &ElementGraph,
         current_element: Option<&ElementConnector>,
-        traversed_element_ids: &mut Vec<String>,
+        levels: &mut HashMap<String, u32>,
         level: u32,
         limit: u32,
         direcion: &RelationshipDirection,
@@ -241,8 +261,13 @@ impl ElementGraphParser {
                 return;
             }
 
-            // Marked as traversed
-            traversed_element_ids.push(current_element.get_element_id().to_owned());
+            // Expanded once at its shallowest level, then dropped from the levels.
+            // Elsewhere printed as a reference without its subtree
+            let id = current_element.get_element_id().to_owned();
+            let is_expanded = levels.get(&id) == Some(&level);
+            if is_expanded {
+                levels.remove(&id);
+            }
 
             // Generate token
             let mut output_line = OutputLine::new();
@@ -261,9 +286,10 @@ impl ElementGraphParser {
             }
 
             //Value
-            output_line.push(OutputToken::Value(
-                current_element.get_element_id().to_owned(),
-            ));
+            match is_expanded {
+                true => output_line.push(OutputToken::Value(id)),
+                false => output_line.push(OutputToken::Reference(id)),
+            }
 
             //Output
             match direcion {
@@ -271,6 +297,10 @@ impl ElementGraphParser {
                 RelationshipDirection::Child => result.child_lines.push(output_line),
             };
 
+            if !is_expanded {
+                return;
+            }
+
             //Child
             let childs = match direcion {
                 RelationshipDirection::Parent => current_element.get_in_id(),
@@ -279,12 +309,11 @@ impl ElementGraphParser {
 
             for child in childs {
                 let element_id = &child.1;
-                let rel_id = &child.0;
-                let next_element = full_graph.get_connection(&element_id);
+                let next_element = full_graph.get_connection(element_id);
                 Self::dfs(
                     full_graph,
                     next_element,
-                    traversed_element_ids,
+                    levels,
                     level + 1,
                     limit,
                     direcion,
@@ -295,10 +324,63 @@ impl ElementGraphParser {
         }
     }
 
+    // Shallowest level of the target and of every connected element within the limit
+    fn get_levels(
+        full_graph: &ElementGraph,
+        target_element_id: &str,
+        limit: u32,
+        direcion: &RelationshipDirection,
+    ) -> HashMap<String, u32> {
+        if full_graph.get_connection(target_element_id).is_none() {
+            return HashMap::new();
+        }
+        let mut levels: HashMap<String, u32> = full_graph
+            .get_closure_within(target_element_id, direcion, limit, |_| true)
+            .into_iter()
+            .filter(|(id, _)| full_graph.get_connection(id).is_some())
+            .collect();
+        levels.insert(target_element_id.to_owned(), 0);
+        levels
+    }
+
+    // Lines printed without deduplication, memoized per element and level so diamonds
+    // are counted without walking them again
+    fn count_paths(
+        full_graph: &ElementGraph,
+        current_element: Option<&ElementConnector>,
+        level: u32,
+        limit: u32,
+        direcion: &RelationshipDirection,
+        memo: &mut HashMap<(String, u32), u64>,
+    ) -> u64 {
+        let Some(current_element) = current_element else {
+            return 0;
+        };
+        if level > limit {
+            return 0;
+        }
+        let key = (current_element.get_element_id().to_owned(), level);
+        if let Some(count) = memo.get(&key) {
+            return *count;
+        }
+
+        let childs = match direcion {
+            RelationshipDirection::Parent => current_element.get_in_id(),
+            RelationshipDirection::Child => current_element.get_out_id(),
+        };
+        let count = childs.iter().fold(1u64, |count, child| {
+            let next_element = full_graph.get_connection(&child.1);
+            count.saturating_add(Self::count_paths(full_graph, next_element, level + 1, limit, direcion, memo))
+        });
+        memo.insert(key, count);
+        count
+    }
+
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::component::relationship_viewer::OutputToken;
     use crate::model::{
         cubs_model::ModelData,
         element_graph::{ElementConnector, ElementGraph},
@@ -510,4 +592,72 @@ mod tests {
 
         assert!(true);
     }
+
+    #[test]
+    fn test_build_output_diamond() {
+        // c1 -> {c2, c3} -> c4 -> c5, c4 and c5 reachable through both branches
+        let mut graph = ElementGraph::new();
+        for id in ["c1", "c2", "c3", "c4", "c5"] {
+            graph.add_connector(id);
+        }
+        graph.connect("r1", "c1", "c2");
+        graph.connect("r2", "c1", "c3");
+        graph.connect("r3", "c2", "c4");
+        graph.connect("r4", "c3", "c4");
+        graph.connect("r5", "c4", "c5");
+
+        let output = ElementGraphParser::build_output(&graph, "c1", &ModelData::default()).unwrap();
+
+        // c4 is printed once, then referenced without its subtree
+        let values: Vec<String> = output
+            .child_lines
+            .iter()
+            .filter_map(|l| match l.line.last() {
+                Some(OutputToken::Value(v)) => Some(format!("v:{}", v)),
+                Some(OutputToken::Reference(v)) => Some(format!("r:{}", v)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(values, vec!["v:c1", "v:c2", "v:c4", "v:c5", "v:c3", "r:c4"]);
+        assert_eq!(output.child_count.distinct, 5);
+        assert_eq!(output.child_count.total, 7);
+        assert_eq!(output.parent_count.distinct, 1);
+        assert_eq!(output.parent_count.total, 1);
+    }
+
+    #[test]
+    fn test_build_output_deep_path_first() {
+        // c1 -> c2 -> c3 -> c4 -> c5 -> c6 walked first reaches c6 at the level limit,
+        // c1 -> c6 -> c7 reaches it again one level down
+        let mut graph = ElementGraph::new();
+        for id in ["c1", "c2", "c3", "c4", "c5", "c6", "c7"] {
+            graph.add_connector(id);
+        }
+        graph.connect("r1", "c1", "c2");
+        graph.connect("r2", "c2", "c3");
+        graph.connect("r3", "c3", "c4");
+        graph.connect("r4", "c4", "c5");
+        graph.connect("r5", "c5", "c6");
+        graph.connect("r6", "c1", "c6");
+        graph.connect("r7", "c6", "c7");
+
+        // Subgraph keeps c6 with its child
+        let parse_graph = ElementGraphParser::parse_graph(&graph, "c1", 0, 5).unwrap();
+        assert_eq!(parse_graph.get_connection("c6").unwrap().get_out_id().len(), 1);
+        assert!(parse_graph.get_connection("c7").is_some());
+
+        let output = ElementGraphParser::build_output(&parse_graph, "c1", &ModelData::default()).unwrap();
+        let values: Vec<String> = output
+            .child_lines
+            .iter()
+            .filter_map(|l| match l.line.last() {
+                Some(OutputToken::Value(v)) => Some(format!("v:{}", v)),
+                Some(OutputToken::Reference(v)) => Some(format!("r:{}", v)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(values, vec!["v:c1", "v:c2", "v:c3", "v:c4", "v:c5", "r:c6", "v:c6", "v:c7"]);
+        assert_eq!(output.child_count.distinct, 7);
+        assert_eq!(output.child_count.total, 8);
+    }
 }
@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+use crate::model::cubs_model::{Element, FacetType};
+
+// Separator of typed column lists, written "\," inside a pointer
+pub const COLUMN_SEPARATOR: char = ',';
+const ESCAPE_CHAR: char = '\\';
+const FIXED_COLUMNS: [&str; 3] = ["id", "name", "type"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableColumn {
+    // Value of the facet type selector
+    pub facet_type: String,
+    pub pointer: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ElementTable {
+    pub columns: Vec<TableColumn>,
+    pub rows: Vec<TableRow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableRow {
+    pub id: String,
+    pub name: String,
+    pub type_: String,
+    // One value per column, null when the pointer does not resolve
+    pub values: Vec<Value>,
+}
+
+impl TableColumn {
+    // Column from "facetType:/pointer", the pointer is kept as is
+    pub fn parse(value: &str) -> Result<TableColumn, String> {
+        let column = match value.split_once(':') {
+            Some((facet_type, pointer)) => TableColumn {
+                facet_type: facet_type.trim().to_string(),
+                pointer: pointer.to_string(),
+            },
+            None => return Err(format!("Invalid column {}", value)),
+        };
+        column.validate()?;
+        Ok(column)
+    }
+
+    // Columns typed as "facetType:/pointer" separated by commas
+    pub fn parse_list(value: &str) -> Result<Vec<TableColumn>, String> {
+        split_list(value)
+            .iter()
+            .map(|c| TableColumn::parse(c))
+            .collect()
+    }
+
+    // Columns sent as a json array
+    pub fn from_json(value: &str) -> Result<Vec<TableColumn>, String> {
+        let columns: Vec<TableColumn> =
+            serde_json::from_str(value).map_err(|e| format!("Invalid columns {}", e))?;
+        columns.iter().try_for_each(TableColumn::validate)?;
+        Ok(columns)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match FacetType::from_form_value(&self.facet_type).is_some() && !self.pointer.is_empty() {
+            true => Ok(()),
+            false => Err(format!("Invalid column {}", self.form_value())),
+        }
+    }
+
+    pub fn form_value(&self) -> String {
+        format!("{}:{}", self.facet_type, self.pointer)
+    }
+}
+
+// Items of a typed list split on unescaped separators. Leading whitespace is dropped,
+// trailing whitespace is kept as it can be part of a pointer
+pub fn split_list(value: &str) -> Vec<String> {
+    let mut items = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE_CHAR => items.last_mut().unwrap().extend(chars.next()),
+            COLUMN_SEPARATOR => items.push(String::new()),
+            c => items.last_mut().unwrap().push(c),
+        }
+    }
+    items
+        .into_iter()
+        .map(|i| i.trim_start().to_string())
+        .filter(|i| !i.is_empty())
+        .collect()
+}
+
+impl TableRow {
+    // Text of all the cells, starting with the fixed columns
+    pub fn get_cells(&self) -> Vec<String> {
+        [&self.id, &self.name, &self.type_]
+            .into_iter()
+            .cloned()
+            .chain(self.values.iter().map(get_cell_text))
+            .collect()
+    }
+}
+
+impl ElementTable {
+    // One row per element with the value of each column
+    pub fn from_elements(elements: &[&Element], columns: Vec<TableColumn>) -> Self {
+        let facet_types: Vec<Option<FacetType>> = columns
+            .iter()
+            .map(|c| FacetType::from_form_value(&c.facet_type))
+            .collect();
+
+        let rows = elements
+            .iter()
+            .map(|e| TableRow {
+                id: e.id.clone(),
+                name: e.name.clone(),
+                type_: e.type_.clone(),
+                values: columns
+                    .iter()
+                    .zip(&facet_types)
+                    .map(|(column, facet_type)| {
+                        facet_type
+                            .as_ref()
+                            .and_then(|f| e.get_json_value(f, &column.pointer, false))
+                            .unwrap_or(Value::Null)
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        ElementTable { columns, rows }
+    }
+
+    pub fn get_headers(&self) -> Vec<String> {
+        FIXED_COLUMNS
+            .iter()
+            .map(|c| c.to_string())
+            .chain(self.columns.iter().map(|c| c.form_value()))
+            .collect()
+    }
+
+    pub fn to_csv(&self, rows: &[&TableRow]) -> String {
+        let mut lines = vec![to_csv_line(&self.get_headers())];
+        lines.extend(rows.iter().map(|r| to_csv_line(&r.get_cells())));
+        lines.join("\n")
+    }
+}
+
+// Strings are shown without quotes and missing values are empty
+pub fn get_cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+// Numbers are compared by value, other cells by text
+pub fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+pub fn to_csv_line(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|c| {
+            if c.contains([',', '"', '\n']) {
+                format!("\"{}\"", c.replace('"', "\"\""))
+            } else {
+                c.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::cubs_model::ModelData;
+    use serde_json::json;
+
+    #[test]
+    fn test_table_from_elements() {
+        let model: ModelData = serde_json::from_value(json!({
+            "schemaVersion": "1",
+            "modelId": "m1",
+            "siteModelId": "s1",
+            "version": 1,
+            "elements": [
+                {"id": "e1", "type": "Pump", "nature": "Asset", "name": "P, 1", "version": 1,
+                 "facets": {"flow": {"value": 1.5}}},
+                {"id": "e2", "type": "Pump", "nature": "Asset", "version": 1, "facets": {}}
+            ],
+            "relationships": []
+        }))
+        .unwrap();
+        let columns = TableColumn::from_json(
+            r#"[{"facet_type": "facets", "pointer": "/flow/value"},
+                {"facet_type": "coreFacets", "pointer": "/nature"}]"#,
+        )
+        .unwrap();
+        let table = ElementTable::from_elements(&model.get_elements(), columns);
+
+        assert_eq!(table.rows[0].values, vec![json!(1.5), json!("Asset")]);
+        assert_eq!(table.rows[1].values, vec![Value::Null, json!("Asset")]);
+
+        let rows: Vec<&TableRow> = table.rows.iter().collect();
+        assert_eq!(
+            table.to_csv(&rows),
+            "id,name,type,facets:/flow/value,coreFacets:/nature\n\
+             e1,\"P, 1\",Pump,1.5,Asset\n\
+             e2,,Pump,,Asset"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_column() {
+        assert!(TableColumn::parse_list("facets").is_err());
+        assert!(TableColumn::parse_list("other:/a").is_err());
+        assert!(TableColumn::parse_list("").unwrap().is_empty());
+        assert!(TableColumn::from_json(r#"[{"facet_type": "other", "pointer": "/a"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_escaped_columns() {
+        let columns = TableColumn::parse_list(r"facets:/a\,b, facets:/c ").unwrap();
+        assert_eq!(columns[0].pointer, "/a,b");
+        // Trailing whitespace belongs to the key
+        assert_eq!(columns[1].pointer, "/c ");
+    }
+
+    #[test]
+    fn test_compare_cells() {
+        assert_eq!(compare_cells("9", "10"), Ordering::Less);
+        assert_eq!(compare_cells("b", "a"), Ordering::Greater);
+    }
+}
@@ -0,0 +1,408 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::time::Instant;
+
+use crate::model::cubs_model::{Element, FacetType, ModelData};
+use crate::model::model_dict::get_value_type_name;
+use crate::model::value_stats::FILTERED_RESULT_KEY;
+
+const MAX_EXAMPLES: usize = 3;
+const MAX_EXAMPLE_LEN: usize = 60;
+const MAX_DEPTH: usize = 8;
+const MAX_ARRAY_ITEMS: usize = 10;
+// Segment used for the items of an array
+pub const ARRAY_ITEM_SEGMENT: &str = "*";
+const COMMON_FIELDS: [&str; 4] = ["id", "type", "nature", "name"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelSchema {
+    pub types: Vec<TypeSchema>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeSchema {
+    pub type_: String,
+    pub element_count: usize,
+    pub groups: Vec<FacetGroupSchema>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacetGroupSchema {
+    // Value of the facet type selector
+    pub facet_type: String,
+    pub element_count: usize,
+    pub paths: Vec<PathSchema>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathSchema {
+    pub pointer: String,
+    pub value_types: Vec<String>,
+    pub occurrence_count: usize,
+    pub occurrence_ratio: f64,
+    pub examples: Vec<String>,
+}
+
+#[derive(Default)]
+struct PathAccumulator {
+    value_types: BTreeSet<&'static str>,
+    occurrence_count: usize,
+    examples: Vec<String>,
+}
+
+#[derive(Default)]
+struct GroupAccumulator {
+    element_count: usize,
+    paths: BTreeMap<String, PathAccumulator>,
+}
+
+const FACET_TYPES: [FacetType; 3] = [
+    FacetType::CoreFacets,
+    FacetType::Facets,
+    FacetType::DynamicFacets,
+];
+
+impl ModelSchema {
+    // Infer the json paths of each facet group of each element type
+    pub fn from(model: &ModelData) -> Self {
+        let start_time = Instant::now();
+
+        let mut by_type: BTreeMap<&str, Vec<&Element>> = BTreeMap::new();
+        for element in &model.elements {
+            by_type.entry(&element.type_).or_default().push(element);
+        }
+
+        let types = by_type
+            .into_iter()
+            .map(|(type_, elements)| TypeSchema {
+                type_: type_.to_string(),
+                element_count: elements.len(),
+                groups: FACET_TYPES
+                    .iter()
+                    .map(|facet_type| FacetGroupSchema::from_elements(&elements, facet_type))
+                    .filter(|group| !group.paths.is_empty())
+                    .collect(),
+            })
+            .collect();
+
+        //Log time
+        let elapsed_time = start_time.elapsed();
+        println!("[Execution time] ModelSchema::from - {:?}", elapsed_time);
+
+        ModelSchema { types }
+    }
+
+    pub fn get_type(&self, type_: &str) -> Option<&TypeSchema> {
+        self.types.iter().find(|t| t.type_ == type_)
+    }
+}
+
+impl FacetGroupSchema {
+    // Infer the json paths of one facet group. Occurrence ratio is relative to all given elements
+    pub fn from_elements(elements: &[&Element], facet_type: &FacetType) -> Self {
+        let mut group = GroupAccumulator::default();
+
+        for element in elements {
+            let common_fields;
+            let facets: Vec<(&String, &Value)> = match facet_type {
+                FacetType::CoreFacets => {
+                    common_fields = element.get_common_fields_values_map();
+                    element
+                        .core_facets
+                        .iter()
+                        .chain(common_fields.iter())
+                        .collect()
+                }
+                FacetType::Facets => element.facets.iter().collect(),
+                FacetType::DynamicFacets => element.dynamic_facets.iter().collect(),
+            };
+            if facets.is_empty() {
+                continue;
+            }
+            group.element_count += 1;
+
+            let mut seen: HashSet<String> = HashSet::new();
+            for (key, value) in facets {
+                let pointer = format!("/{}", escape_pointer_segment(key));
+                collect_paths(&pointer, value, 1, &mut group, &mut seen);
+            }
+            for pointer in seen {
+                if let Some(path) = group.paths.get_mut(&pointer) {
+                    path.occurrence_count += 1;
+                }
+            }
+        }
+
+        FacetGroupSchema {
+            facet_type: facet_type.form_value().to_string(),
+            element_count: group.element_count,
+            paths: group
+                .paths
+                .into_iter()
+                .map(|(pointer, path)| PathSchema {
+                    pointer,
+                    value_types: path
+                        .value_types
+                        .into_iter()
+                        .map(|t| t.to_string())
+                        .collect(),
+                    occurrence_count: path.occurrence_count,
+                    occurrence_ratio: path.occurrence_count as f64 / elements.len().max(1) as f64,
+                    examples: path.examples,
+                })
+                .collect(),
+        }
+    }
+
+    // Whether a pointer resolves in at least one element. Array indexes match the array item path
+    pub fn matches_pointer(&self, pointer: &str) -> bool {
+        let normalized = normalize_pointer(pointer);
+        self.paths
+            .iter()
+            .any(|p| p.pointer == pointer || p.pointer == normalized)
+    }
+}
+
+// Replace array indexes with the array item segment
+pub fn normalize_pointer(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .map(|segment| {
+            if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                ARRAY_ITEM_SEGMENT
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Pointer usable in a query, array items are suggested with their first index
+pub fn to_query_pointer(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .map(|segment| {
+            if segment == ARRAY_ITEM_SEGMENT {
+                "0"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Facet group and pointer of a node in the query results, from its path starting at the result index.
+// Results of a pointer query are relative to the queried pointer
+pub fn facet_pointer_from_result_path(
+    path: &[String],
+    facet_type: Option<&FacetType>,
+    pointer: &str,
+    is_detail: bool,
+) -> Option<(FacetType, String)> {
+    let key = path.get(1)?;
+    let to_pointer = |segments: &[String]| -> String {
+        segments
+            .iter()
+            .map(|s| format!("/{}", escape_pointer_segment(s)))
+            .collect()
+    };
+
+    match facet_type {
+        Some(facet_type) if !pointer.is_empty() => {
+            if !is_detail {
+                Some((
+                    facet_type.clone(),
+                    format!("{}{}", pointer, to_pointer(&path[1..])),
+                ))
+            } else if key == FILTERED_RESULT_KEY {
+                Some((
+                    facet_type.clone(),
+                    format!("{}{}", pointer, to_pointer(&path[2..])),
+                ))
+            } else if COMMON_FIELDS.contains(&key.as_str()) {
+                Some((FacetType::CoreFacets, to_pointer(&path[1..])))
+            } else {
+                None
+            }
+        }
+        // Results are elements
+        _ => match key.as_str() {
+            "facets" if path.len() > 2 => Some((FacetType::Facets, to_pointer(&path[2..]))),
+            "dynamicFacets" if path.len() > 2 => {
+                Some((FacetType::DynamicFacets, to_pointer(&path[2..])))
+            }
+            "facets" | "dynamicFacets" | "version" => None,
+            _ => Some((FacetType::CoreFacets, to_pointer(&path[1..]))),
+        },
+    }
+}
+
+fn collect_paths(
+    pointer: &str,
+    value: &Value,
+    depth: usize,
+    group: &mut GroupAccumulator,
+    seen: &mut HashSet<String>,
+) {
+    let path = group.paths.entry(pointer.to_string()).or_default();
+    path.value_types.insert(get_value_type_name(value));
+    if path.examples.len() < MAX_EXAMPLES && !matches!(value, Value::Object(_) | Value::Array(_)) {
+        let example = truncate_example(&value.to_string());
+        if !path.examples.contains(&example) {
+            path.examples.push(example);
+        }
+    }
+    seen.insert(pointer.to_string());
+
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_pointer = format!("{}/{}", pointer, escape_pointer_segment(key));
+                collect_paths(&child_pointer, child, depth + 1, group, seen);
+            }
+        }
+        Value::Array(arr) => {
+            let child_pointer = format!("{}/{}", pointer, ARRAY_ITEM_SEGMENT);
+            for child in arr.iter().take(MAX_ARRAY_ITEMS) {
+                collect_paths(&child_pointer, child, depth + 1, group, seen);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn truncate_example(example: &str) -> String {
+    match example.char_indices().nth(MAX_EXAMPLE_LEN) {
+        Some((i, _)) => format!("{}…", &example[..i]),
+        None => example.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn model() -> ModelData {
+        serde_json::from_value(json!({
+            "schemaVersion": "1",
+            "modelId": "m1",
+            "siteModelId": "s1",
+            "version": 1,
+            "elements": [
+                {"id": "e1", "type": "Pump", "nature": "Asset", "version": 1,
+                 "facets": {"flow": {"value": 1.5, "unit": "l/s"}, "tags": ["a", "b"]}},
+                {"id": "e2", "type": "Pump", "nature": "Asset", "version": 1,
+                 "facets": {"flow": {"value": "n/a"}}},
+                {"id": "e3", "type": "Pipe", "nature": "Asset", "version": 1, "facets": {}}
+            ],
+            "relationships": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_infer_paths() {
+        let schema = ModelSchema::from(&model());
+        assert_eq!(schema.types.len(), 2);
+
+        let pump = schema.get_type("Pump").unwrap();
+        assert_eq!(pump.element_count, 2);
+        let facets = pump
+            .groups
+            .iter()
+            .find(|g| g.facet_type == "facets")
+            .unwrap();
+        let path = |p: &str| facets.paths.iter().find(|path| path.pointer == p).unwrap();
+
+        assert_eq!(path("/flow/value").value_types, vec!["number", "string"]);
+        assert_eq!(path("/flow/value").occurrence_ratio, 1.0);
+        assert_eq!(path("/flow/unit").occurrence_ratio, 0.5);
+        assert_eq!(path("/flow/unit").examples, vec!["\"l/s\""]);
+        assert_eq!(path("/tags/*").occurrence_count, 1);
+        assert_eq!(path("/tags/*").examples.len(), 2);
+    }
+
+    #[test]
+    fn test_core_facets_include_common_fields() {
+        let schema = ModelSchema::from(&model());
+        let pipe = schema.get_type("Pipe").unwrap();
+        let core = pipe
+            .groups
+            .iter()
+            .find(|g| g.facet_type == "coreFacets")
+            .unwrap();
+        assert!(core.paths.iter().any(|p| p.pointer == "/name"));
+        assert!(pipe.groups.iter().all(|g| g.facet_type != "facets"));
+    }
+
+    #[test]
+    fn test_matches_pointer() {
+        let model = model();
+        let elements = model.get_elements();
+        let group = FacetGroupSchema::from_elements(&elements, &FacetType::Facets);
+
+        assert_eq!(group.element_count, 2);
+        assert!(group.matches_pointer("/flow/unit"));
+        assert!(group.matches_pointer("/tags/1"));
+        assert!(!group.matches_pointer("/flow/units"));
+        assert_eq!(to_query_pointer("/tags/*"), "/tags/0");
+    }
+
+    #[test]
+    fn test_facet_pointer_from_result_path() {
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let result = |p: &[&str], ft: Option<&FacetType>, ptr: &str, detail: bool| {
+            facet_pointer_from_result_path(&path(p), ft, ptr, detail)
+                .map(|(ft, ptr)| (ft.form_value(), ptr))
+        };
+
+        assert_eq!(
+            result(&["0", "facets", "a/b", "1"], None, "", false),
+            Some(("facets", "/a~1b/1".to_string()))
+        );
+        assert_eq!(
+            result(&["3", "name"], None, "", false),
+            Some(("coreFacets", "/name".to_string()))
+        );
+        assert_eq!(result(&["0", "facets"], None, "", false), None);
+        assert_eq!(
+            result(
+                &["0", "unit"],
+                Some(&FacetType::DynamicFacets),
+                "/flow",
+                false
+            ),
+            Some(("dynamicFacets", "/flow/unit".to_string()))
+        );
+        assert_eq!(
+            result(
+                &["0", "filteredResult", "unit"],
+                Some(&FacetType::Facets),
+                "/flow",
+                true
+            ),
+            Some(("facets", "/flow/unit".to_string()))
+        );
+        assert_eq!(
+            result(&["0", "version"], Some(&FacetType::Facets), "/flow", true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_escape_pointer_segment() {
+        assert_eq!(escape_pointer_segment("a/b~c"), "a~1b~0c");
+    }
+}
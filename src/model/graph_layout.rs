@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+// Spacing between nodes of a layer and between layers
+pub const NODE_SPACING_X: f64 = 160.0;
+pub const LAYER_SPACING_Y: f64 = 110.0;
+const MARGIN: f64 = 80.0;
+
+// Positioned subgraph around a target element, parents above and children below
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphLayout {
+    pub target_id: String,
+    pub nodes: Vec<LayoutNode>,
+    pub edges: Vec<LayoutEdge>,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutNode {
+    pub id: String,
+    pub name: String,
+    pub type_: String,
+    // Negative for ancestors, 0 for the target, positive for descendants
+    pub level: i32,
+    pub x: f64,
+    pub y: f64,
+}
+
+// Edge between node indexes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutEdge {
+    pub relationship_id: String,
+    pub source: usize,
+    pub target: usize,
+}
+
+impl GraphLayout {
+    // Layered layout, each node on the layer of its shortest path from the target
+    // nodes: id, name, type - edges: relationship id, source id, target id
+    pub fn from(
+        target_id: &str,
+        nodes: Vec<(String, String, String)>,
+        edges: &[(String, String, String)],
+    ) -> Self {
+        let index: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _, _))| (id.as_str(), i))
+            .collect();
+        let edges: Vec<LayoutEdge> = edges
+            .iter()
+            .filter_map(|(relationship_id, source, target)| {
+                Some(LayoutEdge {
+                    relationship_id: relationship_id.clone(),
+                    source: *index.get(source.as_str())?,
+                    target: *index.get(target.as_str())?,
+                })
+            })
+            .collect();
+
+        let levels = assign_levels(index.get(target_id).copied(), nodes.len(), &edges);
+        let layers = order_layers(&levels, &nodes, &edges);
+
+        // Coordinates, every layer centered on the widest one
+        let widest = layers.values().map(|l| l.len()).max().unwrap_or(0);
+        let min_level = layers.keys().min().copied().unwrap_or(0);
+        let max_level = layers.keys().max().copied().unwrap_or(0);
+        let width = (widest.max(1) - 1) as f64 * NODE_SPACING_X + 2.0 * MARGIN;
+        let height = (max_level - min_level) as f64 * LAYER_SPACING_Y + 2.0 * MARGIN;
+
+        let mut positions = vec![(0.0, 0.0); nodes.len()];
+        for (level, layer) in &layers {
+            let offset = (widest - layer.len()) as f64 * NODE_SPACING_X / 2.0;
+            for (i, node) in layer.iter().enumerate() {
+                positions[*node] = (
+                    MARGIN + offset + i as f64 * NODE_SPACING_X,
+                    MARGIN + (level - min_level) as f64 * LAYER_SPACING_Y,
+                );
+            }
+        }
+
+        let nodes = nodes
+            .into_iter()
+            .zip(levels)
+            .zip(positions)
+            .map(|(((id, name, type_), level), (x, y))| LayoutNode {
+                id,
+                name,
+                type_,
+                level,
+                x,
+                y,
+            })
+            .collect();
+
+        GraphLayout {
+            target_id: target_id.to_string(),
+            nodes,
+            edges,
+            width,
+            height,
+        }
+    }
+
+    // Distinct node types, sorted for the legend
+    pub fn get_types(&self) -> Vec<String> {
+        let mut types: Vec<String> = self.nodes.iter().map(|n| n.type_.clone()).collect();
+        types.sort();
+        types.dedup();
+        types
+    }
+}
+
+// Stable fill color of a type
+pub fn get_type_color(type_: &str) -> String {
+    let hash = type_
+        .bytes()
+        .fold(7u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    format!("hsl({}, 65%, 72%)", hash % 360)
+}
+
+// Breadth first from the target, down the out edges and up the in edges
+fn assign_levels(target: Option<usize>, node_count: usize, edges: &[LayoutEdge]) -> Vec<i32> {
+    let mut levels: Vec<Option<i32>> = vec![None; node_count];
+    let mut queue = VecDeque::new();
+    if let Some(target) = target {
+        levels[target] = Some(0);
+        queue.push_back(target);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let level = levels[node].unwrap_or(0);
+        // Children only below and parents only above the target
+        for edge in edges {
+            let next = if edge.source == node && level >= 0 {
+                Some((edge.target, level + 1))
+            } else if edge.target == node && level <= 0 {
+                Some((edge.source, level - 1))
+            } else {
+                None
+            };
+            if let Some((next, next_level)) = next {
+                if levels[next].is_none() {
+                    levels[next] = Some(next_level);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    // Nodes only reachable through a sibling stay on the target layer
+    levels.into_iter().map(|l| l.unwrap_or(0)).collect()
+}
+
+// Nodes of each layer ordered by the mean position of their neighbours in the layer
+// closer to the target, which reduces edge crossings
+fn order_layers(
+    levels: &[i32],
+    nodes: &[(String, String, String)],
+    edges: &[LayoutEdge],
+) -> BTreeMap<i32, Vec<usize>> {
+    let mut layers: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+    for (node, level) in levels.iter().enumerate() {
+        layers.entry(*level).or_default().push(node);
+    }
+    for layer in layers.values_mut() {
+        layer.sort_by(|a, b| (&nodes[*a].2, &nodes[*a].1).cmp(&(&nodes[*b].2, &nodes[*b].1)));
+    }
+
+    // Outward from the target layer, descendants then ancestors
+    let mut position = vec![0usize; levels.len()];
+    let outward = std::iter::once(0)
+        .chain((1..).take_while(|l| layers.contains_key(l)))
+        .chain(
+            (1..)
+                .map(|l: i32| -l)
+                .take_while(|l| layers.contains_key(l)),
+        )
+        .collect::<Vec<i32>>();
+    for level in outward {
+        if let Some(layer) = layers.get_mut(&level) {
+            if level != 0 {
+                order_by_barycenter(layer, level - level.signum(), levels, edges, &position);
+            }
+            for (i, node) in layer.iter().enumerate() {
+                position[*node] = i;
+            }
+        }
+    }
+
+    layers
+}
+
+// Stable sort, nodes without neighbours in the inner layer go last
+fn order_by_barycenter(
+    layer: &mut Vec<usize>,
+    inner_level: i32,
+    levels: &[i32],
+    edges: &[LayoutEdge],
+    position: &[usize],
+) {
+    let barycenter = |node: usize| {
+        let neighbours: Vec<usize> = edges
+            .iter()
+            .filter_map(|e| {
+                if e.source == node && levels[e.target] == inner_level {
+                    Some(position[e.target])
+                } else if e.target == node && levels[e.source] == inner_level {
+                    Some(position[e.source])
+                } else {
+                    None
+                }
+            })
+            .collect();
+        match neighbours.is_empty() {
+            true => f64::MAX,
+            false => neighbours.iter().sum::<usize>() as f64 / neighbours.len() as f64,
+        }
+    };
+    let mut keyed: Vec<(f64, usize)> = layer.iter().map(|n| (barycenter(*n), *n)).collect();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    *layer = keyed.into_iter().map(|(_, n)| n).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, type_: &str) -> (String, String, String) {
+        (id.to_string(), id.to_uppercase(), type_.to_string())
+    }
+
+    fn edge(rel: &str, source: &str, target: &str) -> (String, String, String) {
+        (rel.to_string(), source.to_string(), target.to_string())
+    }
+
+    #[test]
+    fn test_layered_layout() {
+        // p -> t -> {b, a} -> c, a and b share the child c
+        let layout = GraphLayout::from(
+            "t",
+            vec![
+                node("t", "Pump"),
+                node("p", "Site"),
+                node("b", "Pipe"),
+                node("a", "Pipe"),
+                node("c", "Valve"),
+            ],
+            &[
+                edge("r1", "p", "t"),
+                edge("r2", "t", "b"),
+                edge("r3", "t", "a"),
+                edge("r4", "a", "c"),
+                edge("r5", "b", "c"),
+                edge("r6", "c", "missing"),
+            ],
+        );
+
+        let levels: Vec<i32> = layout.nodes.iter().map(|n| n.level).collect();
+        assert_eq!(levels, vec![0, -1, 1, 1, 2]);
+        // Edges to nodes outside the subgraph are dropped
+        assert_eq!(layout.edges.len(), 5);
+        // Ancestors above, layers sorted by type then name
+        assert!(layout.nodes[1].y < layout.nodes[0].y);
+        assert!(layout.nodes[3].x < layout.nodes[2].x);
+        assert_eq!(layout.nodes[2].y, layout.nodes[3].y);
+        assert_eq!(layout.width, NODE_SPACING_X + 2.0 * MARGIN);
+        assert_eq!(layout.get_types(), vec!["Pipe", "Pump", "Site", "Valve"]);
+        assert_eq!(get_type_color("Pipe"), get_type_color("Pipe"));
+    }
+}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::cubs_model::{CusObject, Element};
+use crate::model::element_table::to_csv_line;
+use crate::model::model_dict::{generate_element_count_by, ElementCounts};
+
+// Elements reachable from an element, summarized and paged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImpactAnalysis {
+    pub total_count: usize,
+    pub by_type: ElementCounts,
+    pub by_nature: ElementCounts,
+    // Rows of the requested page
+    pub rows: Vec<ImpactRow>,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImpactRow {
+    pub id: String,
+    pub name: String,
+    pub type_: String,
+    pub nature: String,
+    // Relationships from the analysed element
+    pub distance: u32,
+}
+
+impl ImpactAnalysis {
+    // Closure elements with their distance, pages start at 0. Pages past the end
+    // are clamped to the last page
+    pub fn from(closure: &[(&Element, u32)], page: usize, page_size: usize) -> Self {
+        let rows = get_impact_rows(closure);
+        let page_size = page_size.max(1);
+        let page = page.min(rows.len().div_ceil(page_size).saturating_sub(1));
+
+        ImpactAnalysis {
+            total_count: rows.len(),
+            by_type: generate_element_count_by(closure.iter().map(|(e, _)| *e), |e| e.get_type())
+                .unwrap_or_default(),
+            by_nature: generate_element_count_by(closure.iter().map(|(e, _)| *e), |e| {
+                e.get_nature()
+            })
+            .unwrap_or_default(),
+            rows: rows
+                .into_iter()
+                .skip(page.saturating_mul(page_size))
+                .take(page_size)
+                .collect(),
+            page,
+            page_size,
+        }
+    }
+
+    pub fn get_page_count(&self) -> usize {
+        self.total_count.div_ceil(self.page_size.max(1))
+    }
+}
+
+// Nearest first, then by type and name
+pub fn get_impact_rows(closure: &[(&Element, u32)]) -> Vec<ImpactRow> {
+    let mut rows: Vec<ImpactRow> = closure
+        .iter()
+        .map(|(element, distance)| ImpactRow {
+            id: element.id.clone(),
+            name: element.name.clone(),
+            type_: element.type_.clone(),
+            nature: element.nature.clone(),
+            distance: *distance,
+        })
+        .collect();
+    rows.sort_by(|a, b| (a.distance, &a.type_, &a.name).cmp(&(b.distance, &b.type_, &b.name)));
+    rows
+}
+
+pub fn impact_rows_to_csv(rows: &[ImpactRow]) -> String {
+    let headers = ["id", "name", "type", "nature", "distance"].map(|h| h.to_string());
+    let mut lines = vec![to_csv_line(&headers)];
+    lines.extend(rows.iter().map(|r| {
+        to_csv_line(&[
+            r.id.clone(),
+            r.name.clone(),
+            r.type_.clone(),
+            r.nature.clone(),
+            r.distance.to_string(),
+        ])
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn element(id: &str, type_: &str, nature: &str) -> Element {
+        serde_json::from_value(
+            json!({"id": id, "type": type_, "nature": nature, "name": id,
+            "version": 1, "facets": {}}),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_impact_analysis() {
+        let (a, b, c) = (
+            element("a", "Pipe", "Asset"),
+            element("b", "Pump", "Asset"),
+            element("c", "Pipe", "Asset"),
+        );
+        let closure = [(&c, 2), (&b, 1), (&a, 1)];
+
+        let analysis = ImpactAnalysis::from(&closure, 1, 2);
+        assert_eq!(analysis.total_count, 3);
+        assert_eq!(analysis.get_page_count(), 2);
+        assert_eq!(analysis.by_type.value[0].element, "Pipe");
+        assert_eq!(analysis.by_type.value[0].count, 2);
+        assert_eq!(analysis.by_nature.value[0].count, 3);
+        // Second page holds the farthest element
+        assert_eq!(analysis.rows.len(), 1);
+        assert_eq!(analysis.rows[0].id, "c");
+
+        let analysis = ImpactAnalysis::from(&closure, usize::MAX, 2);
+        assert_eq!(analysis.page, 1);
+        assert_eq!(analysis.rows[0].id, "c");
+        let analysis = ImpactAnalysis::from(&closure, usize::MAX, usize::MAX);
+        assert_eq!(analysis.page, 0);
+        assert_eq!(analysis.rows.len(), 3);
+        assert_eq!(ImpactAnalysis::from(&[], usize::MAX, 2).page, 0);
+
+        let csv = impact_rows_to_csv(&get_impact_rows(&closure));
+        assert_eq!(
+            csv,
+            "id,name,type,nature,distance\na,a,Pipe,Asset,1\nb,b,Pump,Asset,1\nc,c,Pipe,Asset,2"
+        );
+    }
+}
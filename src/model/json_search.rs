@@ -0,0 +1,121 @@
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::model::facet_schema::escape_pointer_segment;
+
+// Json pointer of a node path from the root of the document
+pub fn to_pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|s| format!("/{}", escape_pointer_segment(s)))
+        .collect()
+}
+
+// Pointers of the nodes whose key or scalar value contains the text, in document order.
+// Matching is case insensitive
+pub fn find_matches(value: &Value, text: &str) -> Vec<String> {
+    let mut matches = Vec::new();
+    let text = text.to_lowercase();
+    if !text.is_empty() {
+        collect_matches(value, None, &mut String::new(), &text, &mut matches);
+    }
+    matches
+}
+
+// Pointers of all the nodes containing a match
+pub fn get_ancestors(pointers: &[String]) -> HashSet<String> {
+    let mut ancestors = HashSet::new();
+    for pointer in pointers {
+        let mut end = pointer.len();
+        while let Some(i) = pointer[..end].rfind('/') {
+            ancestors.insert(pointer[..i].to_string());
+            end = i;
+        }
+    }
+    ancestors
+}
+
+// Index of the child of an array containing the node of a pointer
+pub fn get_child_index(pointer: &str, parent: &str) -> Option<usize> {
+    pointer
+        .strip_prefix(parent)?
+        .strip_prefix('/')?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn collect_matches(
+    value: &Value,
+    key: Option<&str>,
+    pointer: &mut String,
+    text: &str,
+    matches: &mut Vec<String>,
+) {
+    let is_key_match = key.is_some_and(|k| k.to_lowercase().contains(text));
+    let is_value_match = match value {
+        Value::String(s) => s.to_lowercase().contains(text),
+        Value::Number(_) | Value::Bool(_) | Value::Null => value.to_string().contains(text),
+        Value::Object(_) | Value::Array(_) => false,
+    };
+    if is_key_match || is_value_match {
+        matches.push(pointer.clone());
+    }
+
+    let len = pointer.len();
+    match value {
+        Value::Object(map) => {
+            for (k, child) in map {
+                pointer.push('/');
+                pointer.push_str(&escape_pointer_segment(k));
+                collect_matches(child, Some(k), pointer, text, matches);
+                pointer.truncate(len);
+            }
+        }
+        Value::Array(arr) => {
+            for (i, child) in arr.iter().enumerate() {
+                pointer.push_str(&format!("/{}", i));
+                collect_matches(child, None, pointer, text, matches);
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_find_matches() {
+        let value = json!([
+            {"name": "Pump A", "facets": {"flow": 12, "pumpType": "x"}},
+            {"name": "Pipe", "facets": {"flow": null}}
+        ]);
+
+        assert_eq!(
+            find_matches(&value, "PUMP"),
+            vec!["/0/facets/pumpType", "/0/name"]
+        );
+        assert_eq!(find_matches(&value, "null"), vec!["/1/facets/flow"]);
+        assert!(find_matches(&value, "").is_empty());
+    }
+
+    #[test]
+    fn test_get_child_index() {
+        assert_eq!(get_child_index("/0/tags/120/name", "/0/tags"), Some(120));
+        assert_eq!(get_child_index("/150", ""), Some(150));
+        assert_eq!(get_child_index("/0/tagsx/1", "/0/tags"), None);
+    }
+
+    #[test]
+    fn test_get_ancestors() {
+        let ancestors = get_ancestors(&["/0/facets/flow".to_string()]);
+        assert_eq!(ancestors.len(), 3);
+        assert!(
+            ancestors.contains("") && ancestors.contains("/0") && ancestors.contains("/0/facets")
+        );
+    }
+}
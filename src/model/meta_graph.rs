@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+// Element types connected by relationship types, with counts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetaGraph {
+    // Sorted by descending relationship count
+    pub edges: Vec<MetaEdge>,
+    // Element count of each type, sorted by type
+    pub type_counts: Vec<(String, usize)>,
+}
+
+// Relationships of one type from elements of a source type to elements of a target type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaEdge {
+    pub source_type: String,
+    pub relationship_type: String,
+    pub target_type: String,
+    pub count: usize,
+    // Source elements with at least one of these relationships
+    pub source_count: usize,
+    // Out degree over every element of the source type, 0 for those without any
+    pub min_out_degree: usize,
+    pub max_out_degree: usize,
+    pub avg_out_degree: f64,
+}
+
+// Type node of the diagram
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaNode {
+    pub type_: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl MetaGraph {
+    // Relationships as source id, source type, relationship type and target type
+    pub fn from(
+        type_counts: HashMap<String, usize>,
+        relationships: &[(&str, &str, &str, &str)],
+    ) -> Self {
+        // Out degree of each source element, per (source type, relationship type, target type)
+        let mut degrees: HashMap<(&str, &str, &str), HashMap<&str, usize>> = HashMap::new();
+        for (source_id, source_type, relationship_type, target_type) in relationships {
+            *degrees
+                .entry((*source_type, *relationship_type, *target_type))
+                .or_default()
+                .entry(*source_id)
+                .or_default() += 1;
+        }
+
+        let mut edges: Vec<MetaEdge> = degrees
+            .into_iter()
+            .map(|((source_type, relationship_type, target_type), sources)| {
+                let count: usize = sources.values().sum();
+                let type_count = type_counts
+                    .get(source_type)
+                    .copied()
+                    .unwrap_or(0)
+                    .max(sources.len());
+                let min_out_degree = match sources.len() < type_count {
+                    true => 0,
+                    false => sources.values().min().copied().unwrap_or(0),
+                };
+                MetaEdge {
+                    source_type: source_type.to_string(),
+                    relationship_type: relationship_type.to_string(),
+                    target_type: target_type.to_string(),
+                    count,
+                    source_count: sources.len(),
+                    min_out_degree,
+                    max_out_degree: sources.values().max().copied().unwrap_or(0),
+                    avg_out_degree: count as f64 / type_count.max(1) as f64,
+                }
+            })
+            .collect();
+        edges.sort_by(|a, b| {
+            b.count.cmp(&a.count).then_with(|| {
+                (&a.source_type, &a.relationship_type, &a.target_type).cmp(&(
+                    &b.source_type,
+                    &b.relationship_type,
+                    &b.target_type,
+                ))
+            })
+        });
+
+        let type_counts: BTreeMap<String, usize> = type_counts.into_iter().collect();
+        MetaGraph {
+            edges,
+            type_counts: type_counts.into_iter().collect(),
+        }
+    }
+
+    // Types connected by an edge placed on a circle, sorted by type
+    pub fn get_circle_layout(&self, radius: f64) -> Vec<MetaNode> {
+        let types: BTreeSet<&String> = self
+            .edges
+            .iter()
+            .flat_map(|e| [&e.source_type, &e.target_type])
+            .collect();
+        let step = std::f64::consts::TAU / types.len().max(1) as f64;
+        types
+            .into_iter()
+            .enumerate()
+            .map(|(i, type_)| {
+                // From the top, clockwise
+                let angle = i as f64 * step - std::f64::consts::FRAC_PI_2;
+                MetaNode {
+                    type_: type_.clone(),
+                    x: radius + radius * angle.cos(),
+                    y: radius + radius * angle.sin(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn get_type_count(&self, type_: &str) -> usize {
+        self.type_counts
+            .iter()
+            .find(|(t, _)| t == type_)
+            .map_or(0, |(_, count)| *count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_graph() {
+        let type_counts = HashMap::from([
+            ("Pump".to_string(), 3),
+            ("Pipe".to_string(), 4),
+            ("Room".to_string(), 1),
+        ]);
+        let graph = MetaGraph::from(
+            type_counts,
+            &[
+                ("p1", "Pump", "feeds", "Pipe"),
+                ("p1", "Pump", "feeds", "Pipe"),
+                ("p2", "Pump", "feeds", "Pipe"),
+                ("r1", "Room", "contains", "Pump"),
+                ("r1", "Room", "contains", "Pump"),
+            ],
+        );
+
+        let feeds = &graph.edges[0];
+        assert_eq!(
+            (feeds.source_type.as_str(), feeds.count, feeds.source_count),
+            ("Pump", 3, 2)
+        );
+        // p3 has no feeds relationship
+        assert_eq!((feeds.min_out_degree, feeds.max_out_degree), (0, 2));
+        assert_eq!(feeds.avg_out_degree, 1.0);
+
+        let contains = &graph.edges[1];
+        assert_eq!((contains.min_out_degree, contains.max_out_degree), (2, 2));
+        assert_eq!(graph.get_type_count("Pipe"), 4);
+
+        let layout = graph.get_circle_layout(100.0);
+        let types: Vec<&str> = layout.iter().map(|n| n.type_.as_str()).collect();
+        assert_eq!(types, vec!["Pipe", "Pump", "Room"]);
+        assert!((layout[0].x - 100.0).abs() < 1e-9 && layout[0].y.abs() < 1e-9);
+    }
+}
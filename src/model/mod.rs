@@ -1,6 +1,22 @@
 pub mod cubs_model;
 pub mod model_dict;
 pub mod model_error;
+pub mod value_stats;
+pub mod facet_schema;
+pub mod json_search;
+pub mod element_table;
+pub mod aggregation;
+pub mod search_index;
+pub mod view_link;
+pub mod saved_query;
+pub mod element_detail;
+pub mod element_diff;
+pub mod graph_layout;
+pub mod relationship_tree;
+pub mod containment;
+pub mod meta_graph;
+pub mod impact;
+pub mod cache_info;
 #[cfg(feature = "ssr")]
 pub mod parser;
 #[cfg(feature = "ssr")]
@@ -8,8 +24,12 @@ pub mod database_util;
 #[cfg(feature = "ssr")]
 pub mod app_state;
 #[cfg(feature = "ssr")]
+pub mod disk_cache;
+#[cfg(feature = "ssr")]
 pub mod element_graph;
 #[cfg(feature = "ssr")]
 pub mod element_parser;
 #[cfg(feature = "ssr")]
-pub mod element_graph_parser;
\ No newline at end of file
+pub mod element_graph_parser;
+#[cfg(feature = "ssr")]
+pub mod saved_query_store;
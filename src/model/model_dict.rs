@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::time::Instant;
 
 use super::cubs_model::ModelData;
-use crate::model::cubs_model::{CusObject, Element, Relationship};
+use crate::model::cubs_model::{CusObject, Element, FacetType, Relationship};
+
+// Key of elements which do not have a value at the pointer
+pub const MISSING_VALUE_KEY: &str = "<missing>";
+const VALUE_DISPLAY_MAX_LEN: usize = 60;
 
 #[derive(Debug, Serialize)]
 pub struct ModelDictionary {
@@ -181,6 +187,147 @@ pub fn generate_array_field_count(value: &Value, field_name: &str) -> Option<Ele
     Some(ElementCounts { value: counts })
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValueBreakdown {
+    pub facet_type: String,
+    pub pointer: String,
+    pub element_count: usize,
+    pub missing_count: usize,
+    pub values: Vec<ValueCount>,
+    // Json kind of the values: string, number, object...
+    pub by_value_type: ElementCounts,
+    // Elements with a value, by element type
+    pub by_element_type: ElementCounts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueCount {
+    // Key used to filter elements with this value
+    pub key: String,
+    pub display: String,
+    pub value_type: String,
+    pub count: u32,
+}
+
+// Count the distinct values found at the pointer of each element
+pub fn generate_value_breakdown(
+    elements: &[&Element],
+    facet_type: &FacetType,
+    pointer: &str,
+) -> ValueBreakdown {
+    let mut value_counts: HashMap<String, ValueCount> = HashMap::new();
+    let mut type_counts: HashMap<String, u32> = HashMap::new();
+    let mut element_types: Vec<Value> = Vec::new();
+    let mut missing_count = 0;
+
+    for element in elements {
+        let Some(value) = element.get_json_value(facet_type, pointer, false) else {
+            missing_count += 1;
+            continue;
+        };
+
+        element_types.push(json!({ "type": element.type_ }));
+
+        let value_type = get_value_type_name(&value);
+        *type_counts.entry(value_type.to_string()).or_insert(0) += 1;
+        value_counts
+            .entry(get_value_key(&value))
+            .or_insert_with_key(|key| ValueCount {
+                key: key.clone(),
+                display: get_value_display(&value, key),
+                value_type: value_type.to_string(),
+                count: 0,
+            })
+            .count += 1;
+    }
+
+    let mut values: Vec<ValueCount> = value_counts.into_values().collect();
+    values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+
+    let mut by_value_type: Vec<ElementCount> = type_counts
+        .into_iter()
+        .map(|(element, count)| ElementCount { element, count })
+        .collect();
+    by_value_type.sort_by_key(|c| std::cmp::Reverse(c.count));
+
+    ValueBreakdown {
+        facet_type: facet_type.form_value().to_string(),
+        pointer: pointer.to_string(),
+        element_count: elements.len(),
+        missing_count,
+        values,
+        by_value_type: ElementCounts {
+            value: by_value_type,
+        },
+        by_element_type: generate_array_field_count(&Value::Array(element_types), "type")
+            .unwrap_or_default(),
+    }
+}
+
+// Key of the value at the pointer of an element, used by value filter
+pub fn get_element_value_key(element: &Element, facet_type: &FacetType, pointer: &str) -> String {
+    match element.get_json_value(facet_type, pointer, false) {
+        Some(value) => get_value_key(&value),
+        None => MISSING_VALUE_KEY.to_string(),
+    }
+}
+
+// Scalar are keyed by their json, object and array by a hash of their canonical json
+pub fn get_value_key(value: &Value) -> String {
+    match value {
+        Value::Object(_) | Value::Array(_) => {
+            let mut hasher = DefaultHasher::new();
+            to_canonical_json(value).hash(&mut hasher);
+            format!("#{:016x}", hasher.finish())
+        }
+        _ => value.to_string(),
+    }
+}
+
+pub fn get_value_display(value: &Value, key: &str) -> String {
+    match value {
+        Value::Object(_) | Value::Array(_) => {
+            let json = value.to_string();
+            match json.char_indices().nth(VALUE_DISPLAY_MAX_LEN) {
+                Some((i, _)) => format!("{}… {}", &json[..i], key),
+                None => format!("{} {}", json, key),
+            }
+        }
+        _ => key.to_string(),
+    }
+}
+
+pub fn get_value_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+// Json with object keys sorted so equal objects share the same key
+fn to_canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(k, v)| format!("{}:{}", Value::String(k.clone()), to_canonical_json(v)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(arr) => {
+            let items: Vec<String> = arr.iter().map(to_canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
 fn _get_json_array_len(value: &Value) -> u32 {
     if let Some(array) = value.as_array() {
         array.len() as u32
@@ -189,16 +336,16 @@ fn _get_json_array_len(value: &Value) -> u32 {
     }
 }
 
-pub fn generate_element_count_by<T, F>(
-    cubs_objects: &Vec<T>,
+pub fn generate_element_count_by<'a, T, F>(
+    cubs_objects: impl IntoIterator<Item = &'a T>,
     key_getter: F,
 ) -> Option<ElementCounts>
 where
-    T: CusObject,
+    T: CusObject + 'a,
     F: Fn(&T) -> String,
 {
     // Partition into count map
-    let partition_map = cubs_objects.iter().fold(HashMap::new(), |mut acc, obj| {
+    let partition_map = cubs_objects.into_iter().fold(HashMap::new(), |mut acc, obj| {
         let key = key_getter(obj);
         let value = acc.entry(key).or_insert_with(|| 0u32);
         *value += 1;
@@ -224,7 +371,6 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::json;
 
     #[test]
     fn test_valid_array_with_types() {
@@ -258,6 +404,53 @@ mod tests {
         assert!(result.is_none());
     }
 
+    fn element(id: &str, type_: &str, facets: Value) -> Element {
+        serde_json::from_value(json!({
+            "id": id, "type": type_, "nature": "Asset", "version": 1, "facets": facets
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_value_breakdown() {
+        let elements = vec![
+            element("e1", "Pump", json!({"floor": "L1", "spec": {"a": 1, "b": 2}})),
+            element("e2", "Pump", json!({"floor": "L1", "spec": {"b": 2, "a": 1}})),
+            element("e3", "Pipe", json!({"floor": 2, "spec": true})),
+            element("e4", "Pipe", json!({"other": 1})),
+        ];
+        let refs: Vec<&Element> = elements.iter().collect();
+
+        let floors = generate_value_breakdown(&refs, &FacetType::Facets, "/floor");
+        assert_eq!(floors.element_count, 4);
+        assert_eq!(floors.missing_count, 1);
+        assert_eq!(floors.values[0].key, "\"L1\"");
+        assert_eq!(floors.values[0].count, 2);
+        assert_eq!(floors.values[1].key, "2");
+        assert_eq!(floors.values[1].value_type, "number");
+
+        // Objects with same content share a key
+        let specs = generate_value_breakdown(&refs, &FacetType::Facets, "/spec");
+        assert_eq!(specs.values.len(), 2);
+        assert_eq!(specs.values[0].count, 2);
+        assert!(specs.values[0].key.starts_with('#'));
+        assert_eq!(specs.by_value_type.value.len(), 2);
+        // e4 has no spec
+        assert_eq!(specs.by_element_type.value[0].element, "Pump");
+        assert_eq!(specs.by_element_type.value[0].count, 2);
+        assert_eq!(specs.by_element_type.value[1].count, 1);
+    }
+
+    #[test]
+    fn test_element_value_key() {
+        let e = element("e1", "Pump", json!({"floor": "L1"}));
+        assert_eq!(get_element_value_key(&e, &FacetType::Facets, "/floor"), "\"L1\"");
+        assert_eq!(
+            get_element_value_key(&e, &FacetType::Facets, "/missing"),
+            MISSING_VALUE_KEY
+        );
+    }
+
     #[test]
     fn test_array_without_type_fields() {
         let json = json!([{"id": 1}, {"name": "test"}]);
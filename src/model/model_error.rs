@@ -11,6 +11,7 @@ use leptos::{
 pub enum ModelError {
     ModelGraphBuildingError(String),
     ModelNotFound(String),
+    ElementNotFound(String),
     InvalidInput,
     ServerFnError(ServerFnErrorErr),
     ParsingError(String),
@@ -23,6 +24,7 @@ impl fmt::Display for ModelError {
                 write!(f, "Error building model graph: {}", err)
             }
             ModelError::ModelNotFound(err) => write!(f, "Model {} not found", err),
+            ModelError::ElementNotFound(err) => write!(f, "Element {} not found", err),
             ModelError::ServerFnError(_server_fn_error_err) => Ok(()),
             ModelError::InvalidInput => Ok(()),
             ModelError::ParsingError(err) => write!(f, "Unable to parse {}", err),
@@ -1,4 +1,8 @@
-use crate::model::app_state::QuickCache;
+use crate::model::app_state::{AppState, QuickCache};
+use crate::model::disk_cache::DiskCache;
+use crate::model::element_graph::ElementGraph;
+use crate::model::element_parser::ElementConnectorBuilder;
+use crate::model::model_error::ModelError;
 
 use super::cubs_model::{ModelData, ModelResponse, ModelVersionNumber};
 use anyhow::anyhow;
@@ -7,6 +11,7 @@ use sqlx::{Pool, Postgres};
 use std::error::Error;
 use std::io::BufReader;
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 use std::{fs::File, io::Read};
 use uuid::Uuid;
@@ -49,7 +54,7 @@ async fn read_latest_model_data_from_db(
     pg_pool: &sqlx::Pool<sqlx::Postgres>,
     model_id: &String,
     cache: &QuickCache<ModelData>,
-) -> Result<ModelData, Box<dyn Error>> {
+) -> Result<Arc<ModelData>, Box<dyn Error>> {
     let start_time = Instant::now();
 
     println!(
@@ -78,7 +83,9 @@ LIMIT 1"#,
 
     //Convert to ModelData
     println!("[read_model_data_from_db] Convert to internal format ...");
-    let model_data: ModelData = serde_json::from_str(&decompressed_model)?;
+    let mut model_data: ModelData = serde_json::from_str(&decompressed_model)?;
+    model_data.build_element_index();
+    let model_data = Arc::new(model_data);
 
     // Store in cache
     let key = model_id.clone() + "_" + &model_data.version.to_string();
@@ -86,7 +93,7 @@ LIMIT 1"#,
     cache.insert(
         &model_id.clone(),
         &model_data.version.to_string(),
-        &model_data,
+        model_data.clone(),
     );
 
     //Log time
@@ -129,12 +136,27 @@ pub async fn read_model_data_versions(
     Ok(model_versions)
 }
 
+// Resolve the requested version, falling back to the latest saved version when not chosen
+pub async fn resolve_model_version(
+    pg_pool: &sqlx::Pool<sqlx::Postgres>,
+    model_id: &String,
+    vers_no: &str,
+) -> i32 {
+    match vers_no.parse::<i32>() {
+        Ok(version_num) => version_num,
+        Err(_) => read_model_data_versions(pg_pool, model_id)
+            .await
+            .ok()
+            .and_then(|versions| versions.first().map(|v| v.vers_no))
+            .unwrap_or(0),
+    }
+}
+
 async fn read_model_data_from_db_with_version(
     pg_pool: &sqlx::Pool<sqlx::Postgres>,
     model_id: &String,
     version_no: i32,
-    cache: &QuickCache<ModelData>,
-) -> Result<ModelData, Box<dyn Error>> {
+) -> Result<Arc<ModelData>, Box<dyn Error>> {
     let start_time = Instant::now();
 
     println!(
@@ -162,25 +184,15 @@ async fn read_model_data_from_db_with_version(
 
     //Convert to ModelData
     println!("[read_model_data_from_db_with_version] Convert to internal format ...");
-    let model_data: ModelData = serde_json::from_str(&decompressed_model)?;
-
-    // Store in cache
-    let key = model_id.clone() + "_" + &model_data.version.to_string();
-    println!(
-        "[read_model_data_from_db_with_version] Cache Model data key: {}",
-        key
-    );
-    cache.insert(
-        &model_id.clone(),
-        &model_data.version.to_string(),
-        &model_data,
-    );
+    let mut model_data: ModelData = serde_json::from_str(&decompressed_model)?;
+    model_data.build_element_index();
+    let model_data = Arc::new(model_data);
 
     //Log time
     let elapsed_time = start_time.elapsed();
     println!(
-        "[Execution time] {} - {:?}",
-        "read_model_data_from_db_with_version + cache", elapsed_time
+        "[Execution time] read_model_data_from_db_with_version - {:?}",
+        elapsed_time
     );
 
     Ok(model_data)
@@ -189,9 +201,10 @@ async fn read_model_data_from_db_with_version(
 pub async fn read_model_data(
     pg_pool: &sqlx::Pool<sqlx::Postgres>,
     cache: &QuickCache<ModelData>,
+    disk_cache: Option<&DiskCache>,
     model_id: &String,
     version_num: i32,
-) -> Result<ModelData, Box<dyn Error>> {
+) -> Result<Arc<ModelData>, Box<dyn Error>> {
     let start_time = Instant::now();
 
     println!(
@@ -224,9 +237,34 @@ pub async fn read_model_data(
         return Ok(cached_model_data);
     }
 
-    // Get from DB
-    let model_data =
-        read_model_data_from_db_with_version(pg_pool, model_id, version_num, &cache).await;
+    // Load once for all concurrent requests: disk, then DB
+    let model_data = cache
+        .get_or_load(model_id, &version_num.to_string(), async {
+            // Get from disk
+            if let Some(disk_cache) = disk_cache {
+                if let Some(disk_model_data) = disk_cache.read(model_id, version_num).await {
+                    println!("[read_model_data] Found model data {} on disk", model_id);
+                    return Ok(Arc::new(disk_model_data));
+                }
+            }
+
+            // Get from DB
+            let model_data =
+                read_model_data_from_db_with_version(pg_pool, model_id, version_num).await?;
+
+            // Store on disk
+            if let Some(disk_cache) = disk_cache {
+                if let Err(e) = disk_cache
+                    .write(model_id, version_num, model_data.clone())
+                    .await
+                {
+                    eprintln!("[read_model_data] Unable to write model to disk cache: {}", e);
+                }
+            }
+
+            Ok(model_data)
+        })
+        .await;
 
     //Log time
     let elapsed_time = start_time.elapsed();
@@ -238,6 +276,44 @@ pub async fn read_model_data(
     model_data
 }
 
+// Resolve the version and read the model from the caches or DB
+pub async fn read_model(
+    app_state: &AppState,
+    model_id: &str,
+    vers_no: &str,
+) -> Result<Arc<ModelData>, ModelError> {
+    let model_id = model_id.to_string();
+    let pg_pool = app_state.get_pg_pool_ref();
+    let version_num = resolve_model_version(pg_pool, &model_id, vers_no).await;
+    read_model_data(
+        pg_pool,
+        &app_state.get_model_cache(),
+        app_state.get_disk_cache(),
+        &model_id,
+        version_num,
+    )
+    .await
+    .map_err(|e| {
+        eprintln!("[read_model] Unable to read model {} version {}: {}", model_id, version_num, e);
+        ModelError::ModelNotFound(model_id.clone())
+    })
+}
+
+// Graph of the model, built once for all concurrent requests and added to cache
+pub async fn read_model_graph(
+    app_state: &AppState,
+    model_id: &str,
+    model: &ModelData,
+) -> Result<Arc<ElementGraph>, ModelError> {
+    app_state
+        .get_graph_cache()
+        .get_or_load(model_id, &model.version.to_string(), async {
+            println!("[read_model_graph] Building graph of {}", model_id);
+            ElementConnectorBuilder::build_graph(&model.elements, &model.relationships).map(Arc::new)
+        })
+        .await
+}
+
 fn decompress_gzip_to_string(gzip: &Vec<u8>) -> Result<String, Box<dyn Error>> {
     let mut decoder = GzDecoder::new(gzip.as_slice());
     let mut decompressed_data = String::new();
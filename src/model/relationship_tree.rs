@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::model::cubs_model::RelationshipDirection;
+use crate::model::element_detail::RelationshipRow;
+
+// Connected element one level away, with the count of its own connections further away
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeNode {
+    pub relationship: RelationshipRow,
+    pub child_count: usize,
+}
+
+// Levels already loaded on the client, shared by every place an element appears
+#[derive(Debug, Clone, Default)]
+pub struct RelationshipTree {
+    levels: HashMap<String, Vec<TreeNode>>,
+}
+
+impl RelationshipTree {
+    pub fn insert_level(
+        &mut self,
+        id: &str,
+        direction: &RelationshipDirection,
+        nodes: Vec<TreeNode>,
+    ) {
+        self.levels.insert(get_level_key(id, direction), nodes);
+    }
+
+    pub fn get_level(&self, id: &str, direction: &RelationshipDirection) -> Option<&Vec<TreeNode>> {
+        self.levels.get(&get_level_key(id, direction))
+    }
+
+    pub fn is_loaded(&self, id: &str, direction: &RelationshipDirection) -> bool {
+        self.levels.contains_key(&get_level_key(id, direction))
+    }
+
+    pub fn get_loaded_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+}
+
+pub fn get_level_key(id: &str, direction: &RelationshipDirection) -> String {
+    match direction {
+        RelationshipDirection::Parent => format!("<{}", id),
+        RelationshipDirection::Child => format!(">{}", id),
+    }
+}
+
+// Ids from the root to a node, identifies a node of the tree as an element can show up under
+// several parents
+pub fn get_tree_path(parent_path: &str, id: &str) -> String {
+    match parent_path.is_empty() {
+        true => id.to_string(),
+        false => format!("{}/{}", parent_path, id),
+    }
+}
+
+// Expanding an element already on its own path would loop forever
+pub fn is_in_path(path: &str, id: &str) -> bool {
+    path.split('/').any(|p| p == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, child_count: usize) -> TreeNode {
+        TreeNode {
+            relationship: RelationshipRow {
+                relationship_id: format!("r-{}", id),
+                relationship_type: "feeds".to_string(),
+                relationship_name: String::new(),
+                counterpart_id: id.to_string(),
+                counterpart_name: id.to_uppercase(),
+                counterpart_type: "Pump".to_string(),
+            },
+            child_count,
+        }
+    }
+
+    #[test]
+    fn test_relationship_tree() {
+        let mut tree = RelationshipTree::default();
+        tree.insert_level(
+            "a",
+            &RelationshipDirection::Child,
+            vec![node("b", 2), node("c", 0)],
+        );
+
+        // Levels are kept per direction
+        assert!(tree.is_loaded("a", &RelationshipDirection::Child));
+        assert!(!tree.is_loaded("a", &RelationshipDirection::Parent));
+        assert_eq!(
+            tree.get_level("a", &RelationshipDirection::Child).unwrap()[0].child_count,
+            2
+        );
+        assert_eq!(tree.get_loaded_count(), 1);
+
+        let path = get_tree_path(&get_tree_path("", "a"), "b");
+        assert_eq!(path, "a/b");
+        assert!(is_in_path(&path, "a"));
+        assert!(!is_in_path(&path, "ab"));
+
+        tree.clear();
+        assert!(tree.get_level("a", &RelationshipDirection::Child).is_none());
+    }
+}
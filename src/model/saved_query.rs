@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+const MAX_NAME_LENGTH: usize = 100;
+
+// Named element and facet filters of a query, run against any model version
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct SavedQuery {
+    pub id: i32,
+    pub name: String,
+    pub types: String,
+    pub natures: String,
+    pub facet_type: String,
+    pub query: String,
+    pub depth: i32,
+    #[cfg_attr(feature = "ssr", sqlx(rename = "result_limit"))]
+    pub limit: i32,
+}
+
+impl SavedQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("Saved query name is required".to_string());
+        }
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(format!(
+                "Saved query name is longer than {} characters",
+                MAX_NAME_LENGTH
+            ));
+        }
+        if self.depth < 0 || self.limit < 0 {
+            return Err("Depth and limit must be positive".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let query = SavedQuery {
+            name: "Pumps".to_string(),
+            depth: 3,
+            limit: 30,
+            ..Default::default()
+        };
+        assert!(query.validate().is_ok());
+
+        let blank = SavedQuery {
+            name: "  ".to_string(),
+            ..query.clone()
+        };
+        assert!(blank.validate().is_err());
+
+        let long = SavedQuery {
+            name: "q".repeat(MAX_NAME_LENGTH + 1),
+            ..query.clone()
+        };
+        assert!(long.validate().is_err());
+
+        let negative = SavedQuery { depth: -1, ..query };
+        assert!(negative.validate().is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use sqlx::{Pool, Postgres};
+
+use crate::model::saved_query::SavedQuery;
+
+// Saved queries are kept apart from the models in cubs_object_model
+const CREATE_SCHEMA: &str = "CREATE SCHEMA IF NOT EXISTS model_parser";
+const CREATE_TABLE: &str = r#"CREATE TABLE IF NOT EXISTS model_parser.saved_query (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    types TEXT NOT NULL,
+    natures TEXT NOT NULL,
+    facet_type TEXT NOT NULL,
+    query TEXT NOT NULL,
+    depth INTEGER NOT NULL,
+    result_limit INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+)"#;
+
+// Create the saved query schema and table if missing
+pub async fn create_saved_query_table(pg_pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    sqlx::query(CREATE_SCHEMA).execute(pg_pool).await?;
+    sqlx::query(CREATE_TABLE).execute(pg_pool).await?;
+    Ok(())
+}
+
+// Insert the query, replacing the filters of a saved query with the same name
+pub async fn save_query(
+    pg_pool: &Pool<Postgres>,
+    query: &SavedQuery,
+) -> Result<SavedQuery, sqlx::Error> {
+    sqlx::query_as::<_, SavedQuery>(
+        r#"INSERT INTO model_parser.saved_query (name, types, natures, facet_type, query, depth, result_limit)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (name) DO UPDATE SET types = EXCLUDED.types, natures = EXCLUDED.natures,
+            facet_type = EXCLUDED.facet_type, query = EXCLUDED.query, depth = EXCLUDED.depth,
+            result_limit = EXCLUDED.result_limit
+        RETURNING id, name, types, natures, facet_type, query, depth, result_limit"#,
+    )
+    .bind(query.name.trim())
+    .bind(&query.types)
+    .bind(&query.natures)
+    .bind(&query.facet_type)
+    .bind(&query.query)
+    .bind(query.depth)
+    .bind(query.limit)
+    .fetch_one(pg_pool)
+    .await
+}
+
+pub async fn read_saved_queries(pg_pool: &Pool<Postgres>) -> Result<Vec<SavedQuery>, sqlx::Error> {
+    sqlx::query_as::<_, SavedQuery>(
+        r#"SELECT id, name, types, natures, facet_type, query, depth, result_limit
+        FROM model_parser.saved_query ORDER BY name"#,
+    )
+    .fetch_all(pg_pool)
+    .await
+}
+
+// Delete a saved query, false if it does not exist
+pub async fn delete_saved_query(pg_pool: &Pool<Postgres>, id: i32) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM model_parser.saved_query WHERE id = $1")
+        .bind(id)
+        .execute(pg_pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
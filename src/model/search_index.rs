@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use crate::model::cubs_model::{FacetType, ModelData};
+use crate::model::facet_schema::escape_pointer_segment;
+
+const NAME_WEIGHT: f64 = 4.0;
+const TYPE_WEIGHT: f64 = 2.0;
+const NATURE_WEIGHT: f64 = 2.0;
+const FACET_WEIGHT: f64 = 1.0;
+// Whole token matches rank above prefix matches
+const EXACT_MATCH_BOOST: f64 = 2.0;
+const MAX_MATCHES_PER_HIT: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SearchField {
+    Name,
+    Type,
+    Nature,
+    // Index of the facet leaf
+    Facet(u32),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Posting {
+    element: u32,
+    field: SearchField,
+}
+
+// Inverted index of the tokens of element names, types, natures and string facet values
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    // Sorted to find tokens by prefix
+    postings: BTreeMap<String, Vec<Posting>>,
+    // Facet type and pointer of the indexed facet leaves
+    facet_leaves: Vec<(FacetType, String)>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub hits: Vec<SearchHit>,
+    pub total_hit_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub id: String,
+    pub name: String,
+    pub type_: String,
+    pub nature: String,
+    pub score: f64,
+    // Fields with a match, facet fields as "facetType:/pointer"
+    pub matches: Vec<String>,
+}
+
+struct SearchTerm {
+    // None searches all fields
+    field: Option<String>,
+    token: String,
+}
+
+impl SearchIndex {
+    pub fn from(model: &ModelData) -> Self {
+        let start_time = Instant::now();
+        let mut index = SearchIndex::default();
+
+        for (i, element) in model.elements.iter().enumerate() {
+            let element_index = i as u32;
+            index.add_text(&element.name, element_index, SearchField::Name);
+            index.add_text(&element.type_, element_index, SearchField::Type);
+            index.add_text(&element.nature, element_index, SearchField::Nature);
+
+            for (facet_type, facets) in [
+                (FacetType::CoreFacets, &element.core_facets),
+                (FacetType::Facets, &element.facets),
+                (FacetType::DynamicFacets, &element.dynamic_facets),
+            ] {
+                for (key, value) in facets {
+                    let pointer = format!("/{}", escape_pointer_segment(key));
+                    index.add_facet_value(value, &facet_type, pointer, element_index);
+                }
+            }
+        }
+
+        //Log time
+        let elapsed_time = start_time.elapsed();
+        println!("[Execution time] SearchIndex::from - {:?}", elapsed_time);
+
+        index
+    }
+
+    // Elements matching all terms of the query, best first.
+    // Terms are matched on token prefixes and can be scoped as name:, type:, nature: or facets:
+    pub fn search(&self, model: &ModelData, query: &str, limit: usize) -> SearchResult {
+        let terms = parse_query(query);
+        if terms.is_empty() {
+            return SearchResult::default();
+        }
+
+        let mut scores: HashMap<u32, (f64, Vec<SearchField>)> = HashMap::new();
+        for (i, term) in terms.iter().enumerate() {
+            let mut term_scores: HashMap<u32, (f64, Vec<SearchField>)> = HashMap::new();
+            for (token, postings) in self.postings.range(term.token.clone()..) {
+                if !token.starts_with(&term.token) {
+                    break;
+                }
+                let boost = if *token == term.token {
+                    EXACT_MATCH_BOOST
+                } else {
+                    1.0
+                };
+                for posting in postings {
+                    if !is_field_match(&term.field, posting.field) {
+                        continue;
+                    }
+                    let (score, fields) = term_scores.entry(posting.element).or_default();
+                    *score += get_field_weight(posting.field) * boost;
+                    if !fields.contains(&posting.field) {
+                        fields.push(posting.field);
+                    }
+                }
+            }
+
+            // Elements must match every term
+            if i == 0 {
+                scores = term_scores;
+            } else {
+                scores.retain(|element, _| term_scores.contains_key(element));
+                for (element, (score, fields)) in scores.iter_mut() {
+                    let (term_score, term_fields) = &term_scores[element];
+                    *score += term_score;
+                    for field in term_fields {
+                        if !fields.contains(field) {
+                            fields.push(*field);
+                        }
+                    }
+                }
+            }
+        }
+
+        let total_hit_count = scores.len();
+        let mut ranked: Vec<(u32, f64, Vec<SearchField>)> =
+            scores.into_iter().map(|(e, (s, f))| (e, s, f)).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let hits = ranked
+            .into_iter()
+            .take(limit)
+            .filter_map(|(element_index, score, fields)| {
+                let element = model.elements.get(element_index as usize)?;
+                Some(SearchHit {
+                    id: element.id.clone(),
+                    name: element.name.clone(),
+                    type_: element.type_.clone(),
+                    nature: element.nature.clone(),
+                    score,
+                    matches: fields
+                        .into_iter()
+                        .take(MAX_MATCHES_PER_HIT)
+                        .map(|f| self.get_field_label(f))
+                        .collect(),
+                })
+            })
+            .collect();
+
+        SearchResult {
+            hits,
+            total_hit_count,
+        }
+    }
+
+    // Approximate heap size in bytes, used to weigh cached indexes
+    pub fn approx_size(&self) -> usize {
+        let postings_size: usize = self
+            .postings
+            .iter()
+            .map(|(token, postings)| {
+                token.len() + postings.len() * std::mem::size_of::<Posting>() + 64
+            })
+            .sum();
+        let leaves_size: usize = self.facet_leaves.iter().map(|(_, p)| p.len() + 32).sum();
+        postings_size + leaves_size
+    }
+
+    fn add_text(&mut self, text: &str, element: u32, field: SearchField) {
+        for token in tokenize(text) {
+            let postings = self.postings.entry(token).or_default();
+            // Tokens repeated in the same field are indexed once
+            let is_indexed = postings
+                .last()
+                .is_some_and(|p| p.element == element && p.field == field);
+            if !is_indexed {
+                postings.push(Posting { element, field });
+            }
+        }
+    }
+
+    fn add_facet_value(
+        &mut self,
+        value: &Value,
+        facet_type: &FacetType,
+        pointer: String,
+        element: u32,
+    ) {
+        match value {
+            Value::String(s) => {
+                self.facet_leaves.push((facet_type.clone(), pointer));
+                let leaf = (self.facet_leaves.len() - 1) as u32;
+                self.add_text(s, element, SearchField::Facet(leaf));
+            }
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let child_pointer = format!("{}/{}", pointer, escape_pointer_segment(key));
+                    self.add_facet_value(child, facet_type, child_pointer, element);
+                }
+            }
+            Value::Array(arr) => {
+                for (i, child) in arr.iter().enumerate() {
+                    self.add_facet_value(child, facet_type, format!("{}/{}", pointer, i), element);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn get_field_label(&self, field: SearchField) -> String {
+        match field {
+            SearchField::Name => "name".to_string(),
+            SearchField::Type => "type".to_string(),
+            SearchField::Nature => "nature".to_string(),
+            SearchField::Facet(leaf) => {
+                let (facet_type, pointer) = &self.facet_leaves[leaf as usize];
+                format!("{}:{}", facet_type.form_value(), pointer)
+            }
+        }
+    }
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+}
+
+fn parse_query(query: &str) -> Vec<SearchTerm> {
+    query
+        .split_whitespace()
+        .flat_map(|term| {
+            let (field, text) = match term.split_once(':') {
+                Some((field, text)) if is_field_name(field) => (Some(field.to_lowercase()), text),
+                _ => (None, term),
+            };
+            tokenize(text)
+                .map(|token| SearchTerm {
+                    field: field.clone(),
+                    token,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn is_field_name(field: &str) -> bool {
+    matches!(
+        field.to_lowercase().as_str(),
+        "name" | "type" | "nature" | "facets"
+    )
+}
+
+fn is_field_match(field: &Option<String>, posting_field: SearchField) -> bool {
+    matches!(
+        (field.as_deref(), posting_field),
+        (None, _)
+            | (Some("name"), SearchField::Name)
+            | (Some("type"), SearchField::Type)
+            | (Some("nature"), SearchField::Nature)
+            | (Some("facets"), SearchField::Facet(_))
+    )
+}
+
+fn get_field_weight(field: SearchField) -> f64 {
+    match field {
+        SearchField::Name => NAME_WEIGHT,
+        SearchField::Type => TYPE_WEIGHT,
+        SearchField::Nature => NATURE_WEIGHT,
+        SearchField::Facet(_) => FACET_WEIGHT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn model() -> ModelData {
+        serde_json::from_value(json!({
+            "schemaVersion": "1",
+            "modelId": "m1",
+            "siteModelId": "s1",
+            "version": 1,
+            "elements": [
+                {"id": "e1", "type": "Pump", "nature": "Asset", "name": "Main pump", "version": 1,
+                 "facets": {"maker": {"name": "Grundfos"}}},
+                {"id": "e2", "type": "Valve", "nature": "Asset", "name": "Pump valve", "version": 1,
+                 "facets": {}},
+                {"id": "e3", "type": "Room", "nature": "Space", "name": "Plant room", "version": 1,
+                 "facets": {"note": "near the pumps"}}
+            ],
+            "relationships": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let model = model();
+        let index = SearchIndex::from(&model);
+        let result = index.search(&model, "pump", 10);
+
+        assert_eq!(result.total_hit_count, 3);
+        // Name and type exact matches first, facet prefix match last
+        assert_eq!(result.hits[0].id, "e1");
+        assert_eq!(result.hits[2].id, "e3");
+        assert_eq!(result.hits[2].matches, vec!["facets:/note"]);
+    }
+
+    #[test]
+    fn test_field_scoped_search() {
+        let model = model();
+        let index = SearchIndex::from(&model);
+
+        let result = index.search(&model, "type:pump", 10);
+        assert_eq!(result.hits.len(), 1);
+        assert_eq!(result.hits[0].id, "e1");
+
+        let result = index.search(&model, "facets:grund asset", 10);
+        assert_eq!(result.hits.len(), 1);
+        assert_eq!(result.hits[0].matches, vec!["facets:/maker/name", "nature"]);
+
+        assert!(index.search(&model, "name:grundfos", 10).hits.is_empty());
+        assert!(index.search(&model, "", 10).hits.is_empty());
+    }
+}
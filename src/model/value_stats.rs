@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const PERCENTILES: [u32; 5] = [5, 25, 75, 95, 99];
+const MAX_HISTOGRAM_BINS: usize = 20;
+// Key of the values kept by the output filter of a detail query result
+pub const FILTERED_RESULT_KEY: &str = "filteredResult";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NumericStats {
+    pub count: usize,
+    pub null_count: usize,
+    pub non_numeric_count: usize,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<HistogramBin>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Percentile {
+    pub percentile: u32,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+}
+
+// Compute stats of query results. Results wrapped in FilteredElementResult use their filtered result.
+// Return None when no result is numeric
+pub fn compute_numeric_stats(values: &[Value]) -> Option<NumericStats> {
+    let mut numbers: Vec<f64> = Vec::with_capacity(values.len());
+    let mut null_count = 0;
+    let mut non_numeric_count = 0;
+
+    for value in values {
+        let value = value.get(FILTERED_RESULT_KEY).unwrap_or(value);
+        match value {
+            Value::Null => null_count += 1,
+            Value::Number(n) => match n.as_f64() {
+                Some(n) => numbers.push(n),
+                None => non_numeric_count += 1,
+            },
+            _ => non_numeric_count += 1,
+        }
+    }
+
+    if numbers.is_empty() {
+        return None;
+    }
+
+    numbers.sort_by(|a, b| a.total_cmp(b));
+    let count = numbers.len();
+    let sum: f64 = numbers.iter().sum();
+    let mean = sum / count as f64;
+    let variance = numbers.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / count as f64;
+
+    Some(NumericStats {
+        count,
+        null_count,
+        non_numeric_count,
+        sum,
+        min: numbers[0],
+        max: numbers[count - 1],
+        mean,
+        median: percentile(&numbers, 50.0),
+        std_dev: variance.sqrt(),
+        percentiles: PERCENTILES
+            .iter()
+            .map(|p| Percentile {
+                percentile: *p,
+                value: percentile(&numbers, *p as f64),
+            })
+            .collect(),
+        histogram: histogram(&numbers),
+    })
+}
+
+// Linear interpolation between closest ranks of sorted numbers
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+fn histogram(sorted: &[f64]) -> Vec<HistogramBin> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    if min == max {
+        return vec![HistogramBin {
+            lower: min,
+            upper: max,
+            count: sorted.len(),
+        }];
+    }
+
+    let bin_count = ((sorted.len() as f64).sqrt().ceil() as usize).clamp(1, MAX_HISTOGRAM_BINS);
+    let width = (max - min) / bin_count as f64;
+    let mut bins: Vec<HistogramBin> = (0..bin_count)
+        .map(|i| HistogramBin {
+            lower: min + width * i as f64,
+            upper: min + width * (i + 1) as f64,
+            count: 0,
+        })
+        .collect();
+
+    for n in sorted {
+        let i = (((n - min) / width) as usize).min(bin_count - 1);
+        bins[i].count += 1;
+    }
+    bins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_stats_of_numbers() {
+        let values: Vec<Value> = vec![json!(1), json!(2), json!(3), json!(4)];
+        let stats = compute_numeric_stats(&values).unwrap();
+
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.sum, 10.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 4.0);
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!(stats.median, 2.5);
+        assert!((stats.std_dev - 1.118).abs() < 0.001);
+        assert_eq!(stats.histogram.iter().map(|b| b.count).sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn test_stats_of_wrapped_results_and_invalid_values() {
+        let values = vec![
+            json!({"id": "e1", "filteredResult": 10}),
+            json!({"id": "e2", "filteredResult": null}),
+            json!({"id": "e3", "filteredResult": "n/a"}),
+            json!(30),
+            Value::Null,
+        ];
+        let stats = compute_numeric_stats(&values).unwrap();
+
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.null_count, 2);
+        assert_eq!(stats.non_numeric_count, 1);
+        assert_eq!(stats.mean, 20.0);
+    }
+
+    #[test]
+    fn test_stats_of_single_value() {
+        let stats = compute_numeric_stats(&[json!(5.5)]).unwrap();
+        assert_eq!(stats.median, 5.5);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.histogram.len(), 1);
+        assert_eq!(stats.percentiles[0].value, 5.5);
+    }
+
+    #[test]
+    fn test_no_numeric_value() {
+        assert!(compute_numeric_stats(&[json!("a"), json!({"b": 1})]).is_none());
+        assert!(compute_numeric_stats(&[]).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+// Path segments of the deep link routes
+pub const MODEL_SEGMENT: &str = "model";
+pub const ELEMENT_SEGMENT: &str = "element";
+
+// Deep link to a model version, a selected element and the filters of the last query
+// as /model/:id/:version[/element/:eid][?filter=value&...]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ViewLink {
+    pub model_id: String,
+    pub version: String,
+    pub element_id: Option<String>,
+    pub filters: Vec<(String, String)>,
+}
+
+impl ViewLink {
+    // Link from the unescaped segments of a path, None for paths of other routes
+    pub fn from_segments(segments: &[String], filters: Vec<(String, String)>) -> Option<Self> {
+        let (model_id, version, element_id) = match segments {
+            [model, id, version] if model == MODEL_SEGMENT => (id, version, None),
+            [model, id, version, element, eid]
+                if model == MODEL_SEGMENT && element == ELEMENT_SEGMENT =>
+            {
+                (id, version, Some(eid.clone()))
+            }
+            _ => return None,
+        };
+        if model_id.is_empty() || version.is_empty() {
+            return None;
+        }
+
+        Some(ViewLink {
+            model_id: model_id.clone(),
+            version: version.clone(),
+            element_id: element_id.filter(|e| !e.is_empty()),
+            filters,
+        })
+    }
+
+    // Path with the segments and filters escaped with the given function
+    pub fn to_path(&self, escape: impl Fn(&str) -> String) -> String {
+        let mut path = format!(
+            "/{}/{}/{}",
+            MODEL_SEGMENT,
+            escape(&self.model_id),
+            escape(&self.version)
+        );
+        if let Some(element_id) = &self.element_id {
+            path.push_str(&format!("/{}/{}", ELEMENT_SEGMENT, escape(element_id)));
+        }
+
+        let filters: Vec<String> = self
+            .filters
+            .iter()
+            .map(|(name, value)| format!("{}={}", escape(name), escape(value)))
+            .collect();
+        if !filters.is_empty() {
+            path.push('?');
+            path.push_str(&filters.join("&"));
+        }
+        path
+    }
+
+    // Whether the link selects another element than the one selected. Links pushed by
+    // an in-app selection select the same element and leave the view as it is
+    pub fn is_selection_change(&self, selected_id: &str) -> bool {
+        self.element_id.as_deref().unwrap_or_default() != selected_id
+    }
+
+    pub fn get_filter(&self, name: &str) -> Option<&str> {
+        self.filters
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+// Segments of a path, empty segments removed
+pub fn get_path_segments(path: &str, unescape: impl Fn(&str) -> String) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(unescape)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(path: &str) -> Vec<String> {
+        get_path_segments(path, |s| s.replace("%20", " "))
+    }
+
+    #[test]
+    fn test_parse_link() {
+        let link = ViewLink::from_segments(&segments("/model/m1/12"), vec![]).unwrap();
+        assert_eq!(link.model_id, "m1");
+        assert_eq!(link.version, "12");
+        assert_eq!(link.element_id, None);
+
+        let filters = vec![("types".to_string(), "Room".to_string())];
+        let link =
+            ViewLink::from_segments(&segments("/model/m1/12/element/e%201/"), filters).unwrap();
+        assert_eq!(link.element_id.as_deref(), Some("e 1"));
+        assert_eq!(link.get_filter("types"), Some("Room"));
+        assert_eq!(link.get_filter("natures"), None);
+
+        assert!(ViewLink::from_segments(&segments("/"), vec![]).is_none());
+        assert!(ViewLink::from_segments(&segments("/model/m1"), vec![]).is_none());
+        assert!(ViewLink::from_segments(&segments("/other/m1/12"), vec![]).is_none());
+    }
+
+    #[test]
+    fn test_selection_change() {
+        let link = ViewLink::from_segments(&segments("/model/m1/12/element/e1"), vec![]).unwrap();
+        assert!(!link.is_selection_change("e1"));
+        assert!(link.is_selection_change("e2"));
+        assert!(link.is_selection_change(""));
+
+        let link = ViewLink::from_segments(&segments("/model/m1/12"), vec![]).unwrap();
+        assert!(!link.is_selection_change(""));
+        assert!(link.is_selection_change("e1"));
+    }
+
+    #[test]
+    fn test_link_path() {
+        let link = ViewLink {
+            model_id: "m1".to_string(),
+            version: "3".to_string(),
+            element_id: Some("e 1".to_string()),
+            filters: vec![
+                ("query".to_string(), "/a b".to_string()),
+                ("depth".to_string(), "3".to_string()),
+            ],
+        };
+        let escape = |s: &str| s.replace(' ', "%20").replace('/', "%2F");
+        assert_eq!(
+            link.to_path(escape),
+            "/model/m1/3/element/e%201?query=%2Fa%20b&depth=3"
+        );
+
+        let link = ViewLink {
+            element_id: None,
+            filters: vec![],
+            ..link
+        };
+        assert_eq!(link.to_path(escape), "/model/m1/3");
+    }
+}